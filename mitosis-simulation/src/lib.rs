@@ -0,0 +1,197 @@
+use nannou::prelude::*;
+use nannou::rand::Rng;
+use nannou_egui::egui;
+use std::iter;
+use support::{Bounds, Input, Options, Sketch, SimRng};
+
+struct Cell {
+    coords: Point2,
+    radius: f32,
+    color: Srgba,
+}
+
+impl Cell {
+    fn new(rng: &mut impl Rng, bounds: Bounds, radius: f32, color: Srgba) -> Cell {
+        let coords = support::random_point(rng, bounds);
+        Cell {
+            coords,
+            radius,
+            color
+            // color: srgba(1.,1.,1.,1.)
+        }
+    }
+
+    fn update(&mut self, rng: &mut impl Rng, min_x: f32, max_x: f32, min_y: f32, max_y: f32) {
+        self.coords.x -= rng.gen_range(min_x..=max_x);
+        self.coords.y -= rng.gen_range(min_y..=max_y);
+    }
+
+    fn split(&mut self, rng: &mut impl Rng) -> Cell {
+        self.radius /= 2.;
+        self.color.alpha -= 0.15;
+        self.color.blue = rng.gen_range(0.0 .. 1.0);
+        self.color.red = rng.gen_range(0.0 .. 1.0);
+        self.color.green = rng.gen_range(0.0 .. 1.0);
+        let new_cell = Cell {
+            coords: Point2::new(self.coords.x, self.coords.y),
+            radius: self.radius,
+            color: self.color,
+        };
+        self.coords.x -= rng.gen_range(-self.radius / 2.0..self.radius / 2.);
+        self.coords.y -= rng.gen_range(-self.radius / 2.0..self.radius / 2.);
+        new_cell
+    }
+}
+
+// Size of the window the simulation was made for
+pub const SIZE: (u32, u32) = (1000, 800);
+
+pub struct Model {
+    settings: Settings,
+    cells: Vec<Cell>,
+    bounds: Bounds, // Bounds from the last step, new cells get spawned in here
+    rng: SimRng,
+}
+
+struct Settings {
+    min_move_x: f32,
+    max_move_x: f32,
+    min_move_y: f32,
+    max_move_y: f32,
+    new_cell_radius: f32,
+}
+
+fn random_color(rng: &mut impl Rng) -> Srgba {
+    srgba(rng.gen_range(0.0 .. 1.0), rng.gen_range(0.0 .. 1.0), rng.gen_range(0.0 .. 1.0), 1.)
+}
+
+fn is_inside_circle(mouse_pos: &Point2, cell: &Cell) -> bool {
+    mouse_pos.distance(cell.coords) <= cell.radius
+}
+
+impl Sketch for Model {
+    // `amount` is how many cells to start with, 5 if not set
+    fn init(bounds: Bounds, options: &Options) -> Model {
+        let settings = Settings {
+            min_move_x: -1.,
+            max_move_x: 1.,
+            min_move_y: -1.,
+            max_move_y: 1.,
+            new_cell_radius: 25.,
+        };
+        let mut rng = support::seeded_rng(options.seed);
+        let cells: Vec<Cell> = iter::repeat_with(|| {
+            let color = random_color(&mut rng);
+            Cell::new(&mut rng, bounds, settings.new_cell_radius, color)
+        }).take(options.amount.unwrap_or(5)).collect();
+        Model {
+            settings,
+            cells,
+            bounds,
+            rng,
+        }
+    }
+
+    fn step(&mut self, bounds: Bounds, _dt: f32) {
+        self.bounds = bounds;
+        let settings = &self.settings;
+        for cell in &mut self.cells {
+            cell.update(
+                &mut self.rng,
+                settings.min_move_x,
+                settings.max_move_x,
+                settings.min_move_y,
+                settings.max_move_y,
+            );
+        }
+    }
+
+    fn draw(&self, draw: &Draw) {
+        for cell in self.cells.iter() {
+            draw.ellipse()
+                .xy(cell.coords)
+                .radius(cell.radius)
+                .color(cell.color)
+                .stroke_weight(1.);
+        }
+    }
+
+    fn handle_input(&mut self, input: &Input) {
+        let (mb, mouse_pos) = match input {
+            Input::MousePressed(mb, mouse_pos) => (mb, mouse_pos),
+            _ => return,
+        };
+        match mb {
+            MouseButton::Left => {
+                if let Some(rev_index) = self.cells.iter().rev().position(|cell| is_inside_circle(mouse_pos, cell)) {
+                    let index = self.cells.len() - 1 - rev_index; // Apparently index returned by .iter().rev() is reversed...
+                    let new_cell = self.cells[index].split(&mut self.rng);
+                    self.cells.push(new_cell);
+                }
+            }
+            MouseButton::Right => {
+                if let Some(rev_index) = self
+                    .cells
+                    .iter()
+                    .rev()
+                    .position(|cell| is_inside_circle(mouse_pos, cell))
+                {
+                    let index = self.cells.len() - 1 - rev_index; // Apparently index returned by .iter().rev() is reversed...
+                    self.cells.remove(index);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn settings_ui(&mut self, ui: &mut egui::Ui) {
+        let settings = &mut self.settings;
+        ui.label(format!("Amount: {}", self.cells.len()));
+        ui.label("Min move x:");
+        ui.add(egui::Slider::new(&mut settings.max_move_x, 0. ..=5.));
+        ui.label("Max move x:");
+        ui.add(egui::Slider::new(&mut settings.min_move_x, -5. ..=0.));
+        ui.label("Min move y:");
+        ui.add(egui::Slider::new(&mut settings.max_move_y, 0. ..=5.));
+        ui.label("Max move y:");
+        ui.add(egui::Slider::new(&mut settings.min_move_y, -5. ..=0.));
+        ui.label("New cell radius:");
+        ui.add(egui::Slider::new(&mut settings.new_cell_radius,  1.0..=100.));
+
+        let spawn_cell_clicked = ui.button("Spawn cell").clicked();
+        let clear_clicked = ui.button("Clear cells").clicked();
+
+        if spawn_cell_clicked {
+            let color = random_color(&mut self.rng);
+            self.cells.push(Cell::new(&mut self.rng, self.bounds, settings.new_cell_radius, color))
+        } else if clear_clicked {
+            self.cells.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Same as `nannou-projects simulate`, no window needed
+    #[test]
+    fn runs_headless() {
+        let bounds = Bounds::new(SIZE.0, SIZE.1);
+        let mut model: Model = support::run_headless(bounds, &Options { amount: Some(10), seed: 1 }, &[], 600);
+        assert_eq!(model.cells.len(), 10);
+
+        // Clicking a cell splits it in two
+        let cell = model.cells[0].coords;
+        model.handle_input(&Input::MousePressed(MouseButton::Left, cell));
+        assert_eq!(model.cells.len(), 11);
+    }
+
+    #[test]
+    fn same_seed_same_run() {
+        let options = Options { amount: Some(10), seed: 7 };
+        support::assert_same_seed_same_run(Bounds::new(SIZE.0, SIZE.1), &options, &[], |model: &Model| {
+            model.cells.iter().map(|cell| (cell.coords, cell.radius)).collect::<Vec<_>>()
+        });
+    }
+}
@@ -0,0 +1,96 @@
+use nannou::prelude::*;
+use nannou::rand::Rng;
+use support::{Bounds, Options, Sketch, SimRng};
+
+// Size of the window the sketch was made for
+pub const SIZE: (u32, u32) = (1400, 800);
+
+pub struct Model {
+    drops: Vec<Drop>,
+    rng: SimRng,
+}
+
+struct Drop {
+    pos: Point2,
+    z: f32,
+    len: f32,
+    speed: f32,
+}
+
+impl Drop {
+    fn update(&mut self, rng: &mut impl Rng, bounds: Bounds) {
+        self.pos.y -= self.speed;
+
+        let grav = map_range(self.z, 0., 20., 0., 0.2);
+        self.speed += grav;
+
+        if self.pos.y < -(bounds.height as f32) {
+            self.pos.x = support::random_coord(rng, bounds.width * 2);
+            self.pos.y = bounds.half_height();
+            self.speed = map_range(self.z, 0., 20., 3., 8.);
+            self.z = rng.gen_range(0. .. 20.);
+            self.len = map_range(self.z, 0., 20., 1., 20.);
+        }
+        // println!("X: {} Y: {}",self.pos.x, self.pos.y);
+    }
+
+    fn new(rng: &mut impl Rng, bounds: Bounds) -> Drop {
+        // Start offscreen
+        let x = support::random_coord(rng, bounds.width * 2);
+        let y = bounds.half_height() + rng.gen_range(10. .. 250.);
+        let z = rng.gen_range(0. .. 20.);
+        let len = map_range(z, 0., 20., 1., 20.);
+        let pos = Point2::new(x, y);
+        Drop { pos, speed: 1., z, len }
+    }
+}
+
+impl Sketch for Model {
+    // `amount` overrides the default amount of drops
+    fn init(bounds: Bounds, options: &Options) -> Model {
+        let mut drops = Vec::new();
+        let mut rng = support::seeded_rng(options.seed);
+        for _ in 0..options.amount.unwrap_or(2000) {
+            drops.push(Drop::new(&mut rng, bounds))
+        }
+        Model { drops, rng }
+    }
+
+    fn step(&mut self, bounds: Bounds, _dt: f32) {
+        for drop in self.drops.iter_mut() {
+            drop.update(&mut self.rng, bounds);
+        }
+    }
+
+    fn draw(&self, draw: &Draw) {
+        for drop in &self.drops {
+            let mut pos2 = drop.pos;
+            pos2.y += drop.len;
+            let weight = map_range(drop.z,0.,20.,1.,3.);
+            draw.line().start(drop.pos).end(pos2).color(PURPLE).stroke_weight(weight);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Same as `nannou-projects simulate`, no window needed
+    #[test]
+    fn runs_headless() {
+        let bounds = Bounds::new(SIZE.0, SIZE.1);
+        let model: Model = support::run_headless(bounds, &Options { amount: Some(200), seed: 1 }, &[], 600);
+        assert_eq!(model.drops.len(), 200);
+        // Drops that fell out of the bottom went back to the top
+        assert!(model.drops.iter().all(|drop| drop.pos.y >= -(bounds.height as f32)));
+    }
+
+    #[test]
+    fn same_seed_same_run() {
+        let options = Options { amount: Some(200), seed: 7 };
+        support::assert_same_seed_same_run(Bounds::new(SIZE.0, SIZE.1), &options, &[], |model: &Model| {
+            model.drops.iter().map(|drop| (drop.pos, drop.z, drop.len, drop.speed)).collect::<Vec<_>>()
+        });
+    }
+}
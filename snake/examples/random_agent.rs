@@ -0,0 +1,51 @@
+// Shows how the training environment is used, with an agent that picks random actions. Also a quick way to see how
+// many steps per second it manages:
+//
+//   cargo run --release -p snake --example random_agent -- rays
+
+use std::time::Instant;
+
+use rand::Rng;
+use snake::env::{Action, EnvConfig, Encoding, SnakeEnv};
+
+const EPISODES: u64 = 1000;
+
+fn main() {
+    let encoding = match std::env::args().nth(1).as_deref() {
+        None | Some("grid") => Encoding::Grid,
+        Some("rays") => Encoding::Rays,
+        Some(other) => {
+            eprintln!("unknown encoding `{}`, expected grid or rays", other);
+            std::process::exit(1);
+        }
+    };
+    let mut env = SnakeEnv::new(EnvConfig { encoding, ..EnvConfig::default() });
+    let mut rng = support::seeded_rng(0);
+
+    let start = Instant::now();
+    let (mut steps, mut total_reward) = (0u64, 0f64);
+    for episode in 0..EPISODES {
+        env.reset(episode);
+        loop {
+            // A real agent would look at the observation to pick the action
+            let action = Action::ALL[rng.gen_range(0..Action::ALL.len())];
+            let (observation, reward, done) = env.step(action);
+            assert_eq!(observation.data.len(), observation.shape.iter().product::<usize>());
+            total_reward += reward as f64;
+            steps += 1;
+            if done {
+                break;
+            }
+        }
+    }
+
+    let elapsed = start.elapsed();
+    println!(
+        "{} episodes, {} steps in {:?} ({:.0} steps/s), mean reward per episode {:.3}",
+        EPISODES,
+        steps,
+        elapsed,
+        steps as f64 / elapsed.as_secs_f64(),
+        total_reward / EPISODES as f64
+    );
+}
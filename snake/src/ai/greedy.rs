@@ -0,0 +1,17 @@
+use crate::ai::{distance, safe_moves, Obstacles, SnakeController};
+use crate::game::Game;
+use crate::grid::Direction;
+
+// Goes straight for the closest food, only making sure it doesn't die on the very next tick. Quick, but it traps
+// itself once it gets long
+pub struct Greedy;
+
+impl SnakeController for Greedy {
+    fn decide(&mut self, game: &Game, player: usize) -> Direction {
+        let obstacles = Obstacles::new(game);
+        let closest_food = |cell| game.food().iter().map(|food| distance(game, cell, food.cell)).min().unwrap_or(0);
+        safe_moves(game, &obstacles, player)
+            .min_by_key(|(_, cell)| closest_food(*cell))
+            .map_or(game.snakes()[player].direction(), |(direction, _)| direction)
+    }
+}
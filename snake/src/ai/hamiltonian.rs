@@ -0,0 +1,118 @@
+use crate::ai::{safe_moves, Obstacles, ShortestPath, SnakeController};
+use crate::game::Game;
+use crate::grid::{Cell, Direction, Grid};
+
+// Follows a path that goes through every cell once and comes back to the start, so the body is always behind the
+// head on it and the snake can't run into itself. That alone takes forever, so while the snake is short it cuts
+// across the cycle towards food when it can't get past its own tail doing it.
+//
+// Only works if it's turned on from the start of a game (otherwise the body isn't lined up on the cycle), on boards
+// without walls and with an even width or height, and with nobody else on the board. Anywhere else it plays like
+// `ShortestPath`
+#[derive(Default)]
+pub struct Hamiltonian {
+    cycle: Option<Cycle>,
+    fallback: ShortestPath,
+}
+
+// Keeps going forward when the head is closer than this to the tail on the cycle, so food eaten in the meantime
+// (and the segments it adds) can't make the head catch up with the tail
+const SHORTCUT_MARGIN: usize = 4;
+
+struct Cycle {
+    grid: Grid,
+    position: Vec<usize>, // Where every cell is on the cycle, by `Obstacles::index`
+    next: Vec<Direction>, // Which way to go from every cell to stay on the cycle
+}
+
+impl Cycle {
+    // Runs up and down the rows (or columns, if there's an odd number of rows) leaving out the first column, then
+    // comes back down along it
+    fn new(grid: Grid) -> Option<Cycle> {
+        if grid.width < 2 || grid.height < 2 {
+            return None;
+        }
+        let transposed = grid.height % 2 != 0;
+        let (width, height) = if transposed { (grid.height, grid.width) } else { (grid.width, grid.height) };
+        if height % 2 != 0 {
+            return None;
+        }
+
+        let mut cells = vec![Cell::new(0, 0)];
+        for y in 0..height {
+            if y % 2 == 0 {
+                cells.extend((1..width).map(|x| Cell::new(x, y)));
+            } else {
+                cells.extend((1..width).rev().map(|x| Cell::new(x, y)));
+            }
+        }
+        cells.extend((1..height).rev().map(|y| Cell::new(0, y)));
+        if transposed {
+            for cell in &mut cells {
+                *cell = Cell::new(cell.y, cell.x);
+            }
+        }
+
+        let mut position = vec![0; grid.area()];
+        let mut next = vec![Direction::Up; grid.area()];
+        for (i, cell) in cells.iter().enumerate() {
+            let following = cells[(i + 1) % cells.len()];
+            let index = (cell.y * grid.width + cell.x) as usize;
+            position[index] = i;
+            next[index] = Direction::ALL.into_iter().find(|direction| cell.step(*direction) == following)?;
+        }
+        Some(Cycle { grid, position, next })
+    }
+
+    fn index(&self, cell: Cell) -> usize {
+        (cell.y * self.grid.width + cell.x) as usize
+    }
+
+    // Steps forward along the cycle from `a` to `b`
+    fn distance(&self, a: Cell, b: Cell) -> usize {
+        let area = self.position.len();
+        (self.position[self.index(b)] + area - self.position[self.index(a)]) % area
+    }
+}
+
+impl SnakeController for Hamiltonian {
+    fn decide(&mut self, game: &Game, player: usize) -> Direction {
+        let grid = game.grid();
+        if !game.level().walls.is_empty() || game.snakes().len() > 1 {
+            return self.fallback.decide(game, player);
+        }
+        if self.cycle.as_ref().map(|cycle| cycle.grid) != Some(grid) {
+            self.cycle = Cycle::new(grid);
+        }
+        let cycle = match &self.cycle {
+            Some(cycle) => cycle,
+            None => return self.fallback.decide(game, player),
+        };
+
+        let snake = &game.snakes()[player];
+        let head = snake.head();
+        let obstacles = Obstacles::new(game);
+        let mut best = cycle.next[cycle.index(head)];
+        // A snake that's only the head can't turn around, but it also can't be in the way of itself
+        if best == snake.direction().opposite() {
+            best = safe_moves(game, &obstacles, player).next().map_or(snake.direction(), |(direction, _)| direction);
+        }
+
+        if snake.length() < grid.area() / 2 {
+            let area = grid.area();
+            let to_tail = if snake.length() > 1 { cycle.distance(head, snake.tail()) } else { area };
+            let limit = to_tail.saturating_sub(snake.growth() as usize + SHORTCUT_MARGIN);
+            // Closest food along the cycle, not going past it
+            let to_food = game.food().iter().map(|food| cycle.distance(head, food.cell)).min().unwrap_or(0);
+            let mut best_distance = 1;
+            for (direction, cell) in safe_moves(game, &obstacles, player) {
+                let skipped = cycle.distance(head, cell);
+                if skipped > best_distance && skipped < limit && skipped <= to_food {
+                    best = direction;
+                    best_distance = skipped;
+                }
+            }
+        }
+        best
+    }
+}
@@ -0,0 +1,171 @@
+// Bots that play instead of the keyboard. They only look at the `Game`, so the same bot works in the window and
+// headless
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::game::{Config, Game, Outcome};
+use crate::grid::{Boundary, Cell, Direction, Grid};
+
+mod greedy;
+mod hamiltonian;
+mod path;
+
+pub use greedy::Greedy;
+pub use hamiltonian::Hamiltonian;
+pub use path::ShortestPath;
+
+pub trait SnakeController {
+    // Called before every tick for the snake of `player`, it goes that way on the tick
+    fn decide(&mut self, game: &Game, player: usize) -> Direction;
+}
+
+// The built in bots, so front-ends can list them and make new ones
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Strategy {
+    Greedy,
+    ShortestPath,
+    Hamiltonian,
+}
+
+impl Strategy {
+    pub const ALL: [Strategy; 3] = [Strategy::Greedy, Strategy::ShortestPath, Strategy::Hamiltonian];
+
+    // What it's called on the command line and in results other programs read, `parse` goes the other way
+    pub fn id(self) -> &'static str {
+        match self {
+            Strategy::Greedy => "greedy",
+            Strategy::ShortestPath => "shortest-path",
+            Strategy::Hamiltonian => "hamiltonian",
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Strategy::Greedy => "Greedy",
+            Strategy::ShortestPath => "Shortest path",
+            Strategy::Hamiltonian => "Hamiltonian cycle",
+        }
+    }
+
+    pub fn controller(self) -> Box<dyn SnakeController> {
+        match self {
+            Strategy::Greedy => Box::new(Greedy),
+            Strategy::ShortestPath => Box::new(ShortestPath),
+            Strategy::Hamiltonian => Box::new(Hamiltonian::default()),
+        }
+    }
+}
+
+impl FromStr for Strategy {
+    type Err = UnknownStrategy;
+
+    fn from_str(id: &str) -> Result<Strategy, UnknownStrategy> {
+        Strategy::ALL.into_iter().find(|strategy| strategy.id() == id).ok_or_else(|| UnknownStrategy(id.to_string()))
+    }
+}
+
+// An id that isn't one of the bots
+#[derive(Debug)]
+pub struct UnknownStrategy(pub String);
+
+impl fmt::Display for UnknownStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ids: Vec<&str> = Strategy::ALL.iter().map(|strategy| strategy.id()).collect();
+        write!(f, "there's no bot called {}, the bots are {}", self.0, ids.join(", "))
+    }
+}
+
+// How a game played by a bot went
+#[derive(Copy, Clone, Debug)]
+pub struct Summary {
+    pub score: u32,
+    pub length: usize,
+    pub ticks: u32,
+    pub outcome: Option<Outcome>, // None if it was still going after `max_ticks`
+}
+
+// Plays a whole game without drawing anything. Bots can go around in circles forever, so it stops after `max_ticks`
+pub fn play(config: &Config, seed: u64, controller: &mut dyn SnakeController, max_ticks: u32) -> Summary {
+    let mut game = Game::new(config, seed);
+    let mut ticks = 0;
+    while !game.is_over() && ticks < max_ticks {
+        let direction = controller.decide(&game, 0);
+        game.turn(direction);
+        game.tick();
+        ticks += 1;
+    }
+    Summary { score: game.score(), length: game.snake().length(), ticks, outcome: game.outcome() }
+}
+
+// Cells the snake would die on if it moved there on the next tick
+pub(crate) struct Obstacles {
+    grid: Grid,
+    blocked: Vec<bool>,
+}
+
+impl Obstacles {
+    pub fn new(game: &Game) -> Obstacles {
+        let mut obstacles = Obstacles::walls(game);
+        for snake in game.snakes().iter().filter(|snake| snake.is_alive()) {
+            for segment in snake.body() {
+                obstacles.set(segment, true);
+            }
+        }
+        // Tails move out of the way on the same tick, unless the snake is growing
+        for snake in game.snakes().iter().filter(|snake| snake.is_alive() && snake.growth() == 0) {
+            obstacles.set(snake.tail(), false);
+        }
+        obstacles
+    }
+
+    // Only the level's walls, without the snake
+    pub fn walls(game: &Game) -> Obstacles {
+        let grid = game.grid();
+        let mut obstacles = Obstacles { grid, blocked: vec![false; grid.area()] };
+        for wall in &game.level().walls {
+            obstacles.set(*wall, true);
+        }
+        obstacles
+    }
+
+    pub fn index(&self, cell: Cell) -> usize {
+        (cell.y * self.grid.width + cell.x) as usize
+    }
+
+    pub fn is_blocked(&self, cell: Cell) -> bool {
+        self.blocked[self.index(cell)]
+    }
+
+    pub fn set(&mut self, cell: Cell, blocked: bool) {
+        let index = self.index(cell);
+        self.blocked[index] = blocked;
+    }
+}
+
+// Where the snake of `player` can go on the next tick without dying right away. Going back the way it came is never
+// one of them
+pub(crate) fn safe_moves<'a>(
+    game: &'a Game,
+    obstacles: &'a Obstacles,
+    player: usize,
+) -> impl Iterator<Item = (Direction, Cell)> + 'a {
+    let snake = &game.snakes()[player];
+    Direction::ALL
+        .into_iter()
+        .filter(move |direction| *direction != snake.direction().opposite())
+        .filter_map(move |direction| {
+            game.grid().neighbor(snake.head(), direction, game.boundary()).map(|cell| (direction, cell))
+        })
+        .filter(move |(_, cell)| !obstacles.is_blocked(*cell))
+}
+
+// Steps between two cells ignoring anything in the way, the short way through the edge in wrap mode
+pub(crate) fn distance(game: &Game, a: Cell, b: Cell) -> i32 {
+    let grid = game.grid();
+    let (dx, dy) = ((a.x - b.x).abs(), (a.y - b.y).abs());
+    match game.boundary() {
+        Boundary::Solid => dx + dy,
+        Boundary::Wrap => dx.min(grid.width - dx) + dy.min(grid.height - dy),
+    }
+}
@@ -0,0 +1,144 @@
+use std::collections::VecDeque;
+
+use crate::ai::{safe_moves, Obstacles, SnakeController};
+use crate::game::Game;
+use crate::grid::{Cell, Direction};
+
+// Takes the shortest path to food (BFS, every step costs the same so A* wouldn't find anything shorter), but only
+// if the snake can still get to its own tail once it's there. Otherwise it follows its tail until the way is clear
+#[derive(Default)]
+pub struct ShortestPath;
+
+impl SnakeController for ShortestPath {
+    fn decide(&mut self, game: &Game, player: usize) -> Direction {
+        let snake = &game.snakes()[player];
+        let obstacles = Obstacles::new(game);
+        let backwards = Some(snake.direction().opposite());
+
+        let is_food = |cell| game.food().iter().any(|food| food.cell == cell);
+        if let Some(path) = shortest_path(game, &obstacles, snake.head(), backwards, is_food) {
+            if is_safe(game, &path, player) {
+                return path[0].0;
+            }
+        }
+
+        // The tail is always going to move out of the way, so following it buys time
+        if snake.length() > 1 {
+            let tail = snake.tail();
+            if let Some(path) = shortest_path(game, &obstacles, snake.head(), backwards, |cell| cell == tail) {
+                // A growing tail doesn't move, so it can't be stepped on right away
+                if path.len() > 1 || snake.growth() == 0 {
+                    return path[0].0;
+                }
+            }
+        }
+
+        // Boxed in, at least pick the way with the most room
+        safe_moves(game, &obstacles, player)
+            .max_by_key(|(_, cell)| room(game, &obstacles, *cell, snake.length()))
+            .map_or(snake.direction(), |(direction, _)| direction)
+    }
+}
+
+// Breadth first search from `start` to the closest cell `is_goal` likes, the goal itself can be blocked (like the
+// tail of a growing snake). `backwards` is the one way the first step can't go. Returns every step with the cell it
+// leads to
+fn shortest_path(
+    game: &Game,
+    obstacles: &Obstacles,
+    start: Cell,
+    backwards: Option<Direction>,
+    is_goal: impl Fn(Cell) -> bool,
+) -> Option<Vec<(Direction, Cell)>> {
+    let grid = game.grid();
+    let mut came_from: Vec<Option<(Direction, Cell)>> = vec![None; grid.area()];
+    let mut queue = VecDeque::from(vec![start]);
+
+    while let Some(cell) = queue.pop_front() {
+        for direction in Direction::ALL {
+            if cell == start && Some(direction) == backwards {
+                continue;
+            }
+            let next = match grid.neighbor(cell, direction, game.boundary()) {
+                Some(next) => next,
+                None => continue,
+            };
+            if next == start || came_from[obstacles.index(next)].is_some() {
+                continue;
+            }
+            let goal = is_goal(next);
+            if !goal && obstacles.is_blocked(next) {
+                continue;
+            }
+            came_from[obstacles.index(next)] = Some((direction, cell));
+            if goal {
+                let mut path = vec![(direction, next)];
+                let mut current = cell;
+                while current != start {
+                    let (direction, previous) = came_from[obstacles.index(current)].unwrap();
+                    path.push((direction, current));
+                    current = previous;
+                }
+                path.reverse();
+                return Some(path);
+            }
+            queue.push_back(next);
+        }
+    }
+    None
+}
+
+// Moves a copy of the snake along `path` and checks that it could still get to its tail from the end of it. Other
+// snakes are left where they are, there's no telling where they'll go
+fn is_safe(game: &Game, path: &[(Direction, Cell)], player: usize) -> bool {
+    let snake = &game.snakes()[player];
+    let mut body: VecDeque<Cell> = snake.body().collect();
+    let mut growth = snake.growth();
+    for (_, cell) in path {
+        body.push_front(*cell);
+        if growth > 0 {
+            growth -= 1;
+        } else {
+            body.pop_back();
+        }
+    }
+    if body.len() <= 2 {
+        return true;
+    }
+
+    let mut obstacles = Obstacles::walls(game);
+    let others = game.snakes().iter().enumerate().filter(|(other, snake)| *other != player && snake.is_alive());
+    for segment in others.flat_map(|(_, snake)| snake.body()).chain(body.iter().copied()) {
+        obstacles.set(segment, true);
+    }
+    // Going backwards is blocked by the copy's own neck, so there's no need to pass its direction
+    let tail = body[body.len() - 1];
+    shortest_path(game, &obstacles, body[0], None, |cell| cell == tail).is_some()
+}
+
+// How many cells can be reached from `start`, counting at most one more than the snake is long since that's enough
+// room to survive
+fn room(game: &Game, obstacles: &Obstacles, start: Cell, length: usize) -> usize {
+    let grid = game.grid();
+    let enough = length + 1;
+    let mut seen = vec![false; grid.area()];
+    seen[obstacles.index(start)] = true;
+    let mut queue = VecDeque::from(vec![start]);
+    let mut count = 0;
+    while let Some(cell) = queue.pop_front() {
+        count += 1;
+        if count >= enough {
+            break;
+        }
+        for direction in Direction::ALL {
+            if let Some(next) = grid.neighbor(cell, direction, game.boundary()) {
+                let index = obstacles.index(next);
+                if !seen[index] && !obstacles.is_blocked(next) {
+                    seen[index] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+    }
+    count
+}
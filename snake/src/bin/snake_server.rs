@@ -0,0 +1,82 @@
+// Runs networked snake games without a window, players join with J on the title screen:
+//
+//   cargo run --release -p snake --bin snake_server -- --players 3 --level Pillars
+
+use std::process::ExitCode;
+use std::time::Duration;
+
+use clap::Parser;
+use snake::cli::GameArgs;
+use snake::net::{Server, ServerConfig, ServerEvent, DEFAULT_PORT};
+
+#[derive(Parser)]
+#[command(name = "snake_server", about = "Runs networked snake games without a window")]
+struct Args {
+    /// Address to listen on, the port defaults to 7777
+    #[arg(long, default_value = "0.0.0.0")]
+    bind: String,
+    /// How many players have to join before a round starts
+    #[arg(long, default_value_t = 2)]
+    players: usize,
+    #[command(flatten)]
+    game: GameArgs,
+    #[arg(long, default_value_t = 10.)]
+    ticks_per_second: f32,
+    /// Ticks between a turn getting to the server and it happening. Higher hides more lag, but the controls feel
+    /// slower
+    #[arg(long, default_value_t = 3)]
+    input_delay: u32,
+    /// Seconds between the end of a round and the next one
+    #[arg(long, default_value_t = 3.)]
+    round_break: f32,
+    /// Stop after this many rounds instead of going on forever
+    #[arg(long)]
+    rounds: Option<u32>,
+    /// Seed the rounds get theirs from, random if not set
+    #[arg(long)]
+    seed: Option<u64>,
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    if args.players == 0 || args.ticks_per_second <= 0. || args.round_break < 0. {
+        eprintln!("there has to be at least one player, and the tick rate and round break can't be negative");
+        return ExitCode::FAILURE;
+    }
+    let game = match args.game.config(args.players) {
+        Ok(game) => game,
+        Err(err) => {
+            eprintln!("{}: {}", args.game.level.as_deref().unwrap_or_default(), err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let config = ServerConfig {
+        game,
+        ticks_per_second: args.ticks_per_second,
+        input_delay: args.input_delay,
+        round_break: Duration::from_secs_f32(args.round_break),
+        seed: args.seed.unwrap_or_else(support::random_seed),
+    };
+
+    let address = if args.bind.contains(':') { args.bind.clone() } else { format!("{}:{}", args.bind, DEFAULT_PORT) };
+    let mut server = match Server::bind(&address, config) {
+        Ok(server) => server,
+        Err(err) => {
+            eprintln!("couldn't start the server on {}: {}", address, err);
+            return ExitCode::FAILURE;
+        }
+    };
+    server.on_event(|event| match event {
+        ServerEvent::Refused { .. } => eprintln!("{}", event),
+        _ => println!("{}", event),
+    });
+    println!("listening on {}, waiting for {} players", address, args.players);
+
+    let mut round = 1;
+    while args.rounds.is_none_or(|rounds| round <= rounds) {
+        let recording = server.run_round();
+        println!("round {} over after {} ticks, scores {:?}", round, recording.ticks, recording.scores);
+        round += 1;
+    }
+    ExitCode::SUCCESS
+}
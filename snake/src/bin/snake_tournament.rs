@@ -0,0 +1,190 @@
+// Plays the same seeded games with every bot without a window and ranks them by score:
+//
+//   cargo run --release -p snake --bin snake_tournament -- --games 1000
+//   cargo run --release -p snake --bin snake_tournament -- --bots greedy,shortest-path --format csv > results.csv
+
+use std::io;
+use std::process::ExitCode;
+use std::thread;
+
+use clap::{Parser, ValueEnum};
+use serde::Serialize;
+use snake::ai::{self, Strategy};
+use snake::cli::{self, GameArgs};
+use snake::game::{Config, Outcome};
+
+// Long enough for the Hamiltonian bot to fill the default board
+const MAX_TICKS: u32 = 1_000_000;
+
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Table,
+    Csv,
+    Json,
+}
+
+#[derive(Parser)]
+#[command(name = "snake_tournament", about = "Plays the same games with every snake bot and ranks them")]
+struct Args {
+    /// Bots to play, all of them if not set
+    #[arg(long, value_delimiter = ',', value_parser = cli::strategy_parser())]
+    bots: Vec<Strategy>,
+    /// Games every bot plays
+    #[arg(long, default_value_t = 100)]
+    games: u64,
+    /// Seed of the first game, the others count up from it (wrapping around after the largest one). Every bot gets
+    /// the same seeds
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+    #[command(flatten)]
+    game: GameArgs,
+    /// Games still going after this many ticks are stopped, bots can go around in circles forever
+    #[arg(long, default_value_t = MAX_TICKS)]
+    max_ticks: u32,
+    /// How to print the results, csv and json are for reading them with other programs
+    #[arg(long, value_enum, default_value_t = Format::Table)]
+    format: Format,
+}
+
+// How one bot did over all of its games
+#[derive(Serialize)]
+struct Standing {
+    rank: usize,
+    bot: &'static str,
+    name: &'static str,
+    games: u64,
+    mean_score: f64,
+    median_score: f64,
+    max_score: u32,
+    mean_ticks: f64, // Ticks it survived
+    median_ticks: f64,
+    wins: u64, // Games where it filled the board
+    win_rate: f64,
+    still_going: u64, // Games stopped at `--max-ticks`
+}
+
+fn mean(values: &[u32]) -> f64 {
+    values.iter().map(|value| *value as f64).sum::<f64>() / values.len() as f64
+}
+
+fn median(values: &[u32]) -> f64 {
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+    let middle = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (sorted[middle - 1] as f64 + sorted[middle] as f64) / 2.
+    } else {
+        sorted[middle] as f64
+    }
+}
+
+fn play(bot: Strategy, config: &Config, args: &Args) -> Standing {
+    let (mut scores, mut ticks) = (Vec::new(), Vec::new());
+    let (mut wins, mut still_going) = (0, 0);
+    for seed in (0..args.games).map(|i| args.seed.wrapping_add(i)) {
+        // Every game gets a fresh bot, same as in the window
+        let summary = ai::play(config, seed, bot.controller().as_mut(), args.max_ticks);
+        scores.push(summary.score);
+        ticks.push(summary.ticks);
+        match summary.outcome {
+            Some(Outcome::Won) => wins += 1,
+            None => still_going += 1,
+            _ => {}
+        }
+    }
+    Standing {
+        rank: 0,
+        bot: bot.id(),
+        name: bot.name(),
+        games: args.games,
+        mean_score: mean(&scores),
+        median_score: median(&scores),
+        max_score: scores.iter().copied().max().unwrap_or(0),
+        mean_ticks: mean(&ticks),
+        median_ticks: median(&ticks),
+        wins,
+        win_rate: wins as f64 / args.games as f64,
+        still_going,
+    }
+}
+
+fn print_table(standings: &[Standing]) {
+    println!(
+        "{:<4} {:<18} {:>9} {:>9} {:>7} {:>11} {:>11} {:>8} {:>11}",
+        "", "Bot", "Mean", "Median", "Max", "Mean ticks", "Med. ticks", "Won", "Still going"
+    );
+    for standing in standings {
+        println!(
+            "{:<4} {:<18} {:>9.1} {:>9.1} {:>7} {:>11.1} {:>11.1} {:>7.1}% {:>11}",
+            format!("{}.", standing.rank),
+            standing.name,
+            standing.mean_score,
+            standing.median_score,
+            standing.max_score,
+            standing.mean_ticks,
+            standing.median_ticks,
+            standing.win_rate * 100.,
+            standing.still_going
+        );
+    }
+}
+
+// Same columns as the json, in the order of the fields
+fn print_csv(standings: &[Standing]) -> csv::Result<()> {
+    let mut writer = csv::Writer::from_writer(io::stdout());
+    for standing in standings {
+        writer.serialize(standing)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    if args.games == 0 {
+        eprintln!("every bot has to play at least one game");
+        return ExitCode::FAILURE;
+    }
+    let config = match args.game.config(1) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("{}: {}", args.game.level.as_deref().unwrap_or_default(), err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let requested = if args.bots.is_empty() { &Strategy::ALL[..] } else { &args.bots[..] };
+    let mut bots: Vec<Strategy> = Vec::new();
+    for bot in requested {
+        if !bots.contains(bot) {
+            bots.push(*bot);
+        }
+    }
+    // Every bot on its own thread, the games are the same no matter where they're played
+    let mut standings: Vec<Standing> = thread::scope(|scope| {
+        let players: Vec<_> = bots.iter().map(|bot| scope.spawn(|| play(*bot, &config, &args))).collect();
+        players.into_iter().map(|player| player.join().expect("bots don't panic")).collect()
+    });
+    standings.sort_by(|a, b| b.mean_score.total_cmp(&a.mean_score));
+    for (i, standing) in standings.iter_mut().enumerate() {
+        standing.rank = i + 1;
+    }
+
+    match args.format {
+        Format::Table => print_table(&standings),
+        Format::Csv => {
+            if let Err(err) = print_csv(&standings) {
+                eprintln!("couldn't write the results as csv: {}", err);
+                return ExitCode::FAILURE;
+            }
+        }
+        Format::Json => match serde_json::to_string_pretty(&standings) {
+            Ok(json) => println!("{}", json),
+            Err(err) => {
+                eprintln!("couldn't write the results as json: {}", err);
+                return ExitCode::FAILURE;
+            }
+        },
+    }
+    ExitCode::SUCCESS
+}
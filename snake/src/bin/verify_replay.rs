@@ -0,0 +1,53 @@
+// Plays replay files without a window and checks they still end with the score that was recorded, so changes to the
+// game that would break old replays get noticed:
+//
+//   cargo run --release -p snake --bin verify_replay -- ~/.local/share/nannou-projects/snake_replays/*.snkr
+
+use std::path::Path;
+use std::process::ExitCode;
+
+use snake::replay::{Player, Recording};
+
+fn main() -> ExitCode {
+    let paths: Vec<String> = std::env::args().skip(1).collect();
+    if paths.is_empty() {
+        eprintln!("usage: verify_replay <replay.snkr>...");
+        return ExitCode::FAILURE;
+    }
+
+    let mut failed = 0;
+    for path in &paths {
+        let recording = match Recording::load(Path::new(path)) {
+            Ok(recording) => recording,
+            Err(err) => {
+                eprintln!("{}: {}", path, err);
+                failed += 1;
+                continue;
+            }
+        };
+        let (expected_scores, expected_ticks) = (recording.scores.clone(), recording.ticks);
+        let mut player = Player::new(recording);
+        player.finish();
+        let game = player.game();
+        let scores: Vec<u32> = game.snakes().iter().map(|snake| snake.score()).collect();
+        if scores == expected_scores && game.ticks() == expected_ticks {
+            println!("{}: ok, scores {:?} after {} ticks", path, scores, game.ticks());
+        } else {
+            println!(
+                "{}: MISMATCH, recorded scores {:?} after {} ticks but replayed to {:?} after {}",
+                path,
+                expected_scores,
+                expected_ticks,
+                scores,
+                game.ticks()
+            );
+            failed += 1;
+        }
+    }
+
+    if failed > 0 {
+        eprintln!("{} of {} replays failed", failed, paths.len());
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
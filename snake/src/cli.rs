@@ -0,0 +1,46 @@
+// Command line arguments the headless binaries have in common, flattened into their own arguments with clap
+
+use clap::builder::{PossibleValuesParser, TypedValueParser};
+use clap::Args;
+
+use crate::ai::Strategy;
+use crate::food;
+use crate::game::Config;
+use crate::grid::Boundary;
+use crate::level::{self, Level};
+
+// What game gets played
+#[derive(Args)]
+pub struct GameArgs {
+    /// Name of a bundled level or a path to a level file, the open 40x40 board if not set
+    #[arg(long)]
+    pub level: Option<String>,
+    /// Snakes come back in on the opposite side instead of dying at the edge
+    #[arg(long)]
+    pub wrap: bool,
+    /// How much food is on the board at the same time
+    #[arg(long, default_value_t = 1)]
+    pub food: usize,
+    /// Only normal food, no power-ups
+    #[arg(long)]
+    pub classic_food: bool,
+}
+
+impl GameArgs {
+    // Fails if `--level` isn't a bundled level and can't be loaded from a file either
+    pub fn config(&self, players: usize) -> Result<Config, level::Error> {
+        let level = match &self.level {
+            Some(name) => Level::find(name)?,
+            None => Config::default().level,
+        };
+        let boundary = if self.wrap { Boundary::Wrap } else { Boundary::Solid };
+        let food_weights = if self.classic_food { food::CLASSIC_WEIGHTS } else { food::DEFAULT_WEIGHTS };
+        Ok(Config { level, boundary, food_count: self.food, players, food_weights })
+    }
+}
+
+// Takes bots by their id, clap lists the ids in `--help` and suggests one when there's a typo
+pub fn strategy_parser() -> impl TypedValueParser<Value = Strategy> {
+    PossibleValuesParser::new(Strategy::ALL.map(Strategy::id))
+        .map(|id| id.parse().expect("only the ids of the bots get through"))
+}
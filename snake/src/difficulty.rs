@@ -0,0 +1,103 @@
+// How fast the game goes. It speeds up as the snake grows, one level every few segments, and the curve decides how
+// much faster every level is. This only sets the pace front-ends tick at, the game itself plays the same at any speed
+
+use crate::game::Game;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Curve {
+    Linear,      // A bit faster with every segment
+    Stepped,     // Stays the same until the next level, then jumps
+    Exponential, // The same percentage faster every level, slow at first and then very fast
+}
+
+impl Curve {
+    pub const ALL: [Curve; 3] = [Curve::Linear, Curve::Stepped, Curve::Exponential];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Curve::Linear => "Linear",
+            Curve::Stepped => "Stepped",
+            Curve::Exponential => "Exponential",
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SpeedCurve {
+    pub curve: Curve,
+    pub start: f32, // Ticks per second at the start
+    pub max: f32,   // It never gets faster than this
+    // Ticks per second added every level, or for exponential curves how much faster every level is (0.1 is 10%)
+    pub increase: f32,
+    pub segments_per_level: u32,
+}
+
+impl SpeedCurve {
+    // Segments the longest snake has grown, that's what the speed goes by in games with more than one player
+    fn grown(game: &Game) -> u32 {
+        game.snakes().iter().map(|snake| snake.length() as u32 - 1).max().unwrap_or(0)
+    }
+
+    // Starts at 0
+    pub fn level(&self, game: &Game) -> u32 {
+        SpeedCurve::grown(game) / self.segments_per_level.max(1)
+    }
+
+    pub fn ticks_per_second(&self, game: &Game) -> f32 {
+        let progress = SpeedCurve::grown(game) as f32 / self.segments_per_level.max(1) as f32;
+        let ticks_per_second = match self.curve {
+            Curve::Linear => self.start + self.increase * progress,
+            Curve::Stepped => self.start + self.increase * self.level(game) as f32,
+            Curve::Exponential => self.start * (1. + self.increase).powf(progress),
+        };
+        ticks_per_second.min(self.max.max(self.start))
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
+
+    pub fn curve(self) -> SpeedCurve {
+        match self {
+            Difficulty::Easy => {
+                SpeedCurve { curve: Curve::Linear, start: 5., max: 10., increase: 0.5, segments_per_level: 5 }
+            }
+            // Starts at the speed the game had when it moved every 10th frame on a 60hz screen
+            Difficulty::Normal => {
+                SpeedCurve { curve: Curve::Stepped, start: 6., max: 16., increase: 1., segments_per_level: 5 }
+            }
+            Difficulty::Hard => {
+                SpeedCurve { curve: Curve::Exponential, start: 8., max: 30., increase: 0.1, segments_per_level: 3 }
+            }
+        }
+    }
+
+    // The preset `curve` came from, None if it was changed by hand
+    pub fn of(curve: &SpeedCurve) -> Option<Difficulty> {
+        Difficulty::ALL.into_iter().find(|difficulty| difficulty.curve() == *curve)
+    }
+
+    // The next harder one, wrapping around to easy. Curves changed by hand go back to easy too
+    pub fn next(curve: &SpeedCurve) -> Difficulty {
+        match Difficulty::of(curve) {
+            Some(Difficulty::Easy) => Difficulty::Normal,
+            Some(Difficulty::Normal) => Difficulty::Hard,
+            _ => Difficulty::Easy,
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use crate::ai::{SnakeController, Strategy};
+use crate::difficulty::{Difficulty, SpeedCurve};
+use crate::game::Config;
+use crate::grid::{Boundary, Direction};
+use crate::level::Level;
+use crate::session::{Session, State};
+use crate::ticker::Ticker;
+
+// Plays a local session in real time: the clock, the speed curve, the level list and the bot. Front-ends hand it
+// the time between frames and what the keys mean, and draw whatever `session` is at
+pub struct Driver {
+    session: Session,
+    ticker: Ticker, // The snake moves once per tick
+    speed_curve: SpeedCurve, // Sets the ticker's rate as the snake grows
+    levels: Vec<Level>, // Picked on the title screen
+    level_index: usize,
+    autopilot: bool, // The bot plays instead of the first player
+    strategy: Strategy,
+    controller: Box<dyn SnakeController>,
+    bot_played: bool, // If the bot steered at any point of the current game
+    play_time: f32,   // Seconds the current game has been played for, not counting pauses
+}
+
+impl Driver {
+    // Starts on the title screen with the first level. There has to be at least one
+    pub fn new(levels: Vec<Level>, seed: u64) -> Driver {
+        let config = Config { level: levels[0].clone(), ..Config::default() };
+        let speed_curve = Difficulty::Normal.curve();
+        Driver {
+            session: Session::new(config, seed),
+            ticker: Ticker::new(speed_curve.start),
+            speed_curve,
+            levels,
+            level_index: 0,
+            autopilot: false,
+            strategy: Strategy::ShortestPath,
+            controller: Strategy::ShortestPath.controller(),
+            bot_played: false,
+            play_time: 0.,
+        }
+    }
+
+    pub fn session(&self) -> &Session {
+        &self.session
+    }
+
+    pub fn session_mut(&mut self) -> &mut Session {
+        &mut self.session
+    }
+
+    // Plays the ticks that `dt` seconds are worth, with the bot steering before every one if it's on
+    pub fn step(&mut self, dt: f32) {
+        // Paused the ticker too, otherwise the snake would jump ahead when resuming
+        if self.session.state() != State::Playing {
+            return;
+        }
+        self.play_time += dt;
+        // Set every step, the snake might have grown on the last tick
+        self.ticker.ticks_per_second = self.speed_curve.ticks_per_second(self.session.game());
+        for _ in 0..self.ticker.advance(dt * self.session.game().speed()) {
+            if self.autopilot && self.session.state() == State::Playing {
+                let direction = self.controller.decide(self.session.game(), 0);
+                self.session.turn(direction);
+                self.bot_played = true;
+            }
+            self.session.tick();
+        }
+    }
+
+    // A key turning a snake. The first snake's keys do nothing while the bot has it
+    pub fn steer(&mut self, player: usize, direction: Direction) {
+        if player != 0 || !self.autopilot {
+            self.session.turn_player(player, direction);
+        }
+    }
+
+    // How far it is between the last tick and the next one, for drawing
+    pub fn alpha(&self) -> f32 {
+        self.ticker.alpha()
+    }
+
+    pub fn ticks_per_second(&self) -> f32 {
+        self.ticker.ticks_per_second
+    }
+
+    pub fn play_time(&self) -> f32 {
+        self.play_time
+    }
+
+    pub fn restart(&mut self) {
+        self.session.restart();
+        self.reset_game();
+    }
+
+    pub fn back_to_title(&mut self) {
+        self.session.back_to_title();
+        self.reset_game();
+    }
+
+    fn reset_game(&mut self) {
+        self.bot_played = false;
+        self.play_time = 0.;
+        // Bots can remember things about the game they were playing
+        self.controller = self.strategy.controller();
+    }
+
+    pub fn levels(&self) -> &[Level] {
+        &self.levels
+    }
+
+    pub fn level(&self) -> &Level {
+        &self.levels[self.level_index]
+    }
+
+    // Moves `offset` levels forward in the list, wrapping around at the ends
+    pub fn change_level(&mut self, offset: isize) {
+        let count = self.levels.len() as isize;
+        self.level_index = (self.level_index as isize + offset).rem_euclid(count) as usize;
+        self.session.set_level(self.levels[self.level_index].clone());
+    }
+
+    // Swaps one of the levels for another one, the session gets it too if it's the one picked
+    pub fn replace_level(&mut self, index: usize, level: Level) {
+        self.levels[index] = level;
+        if self.level_index == index {
+            self.session.set_level(self.levels[index].clone());
+        }
+    }
+
+    pub fn toggle_boundary(&mut self) {
+        let boundary = match self.session.config().boundary {
+            Boundary::Solid => Boundary::Wrap,
+            Boundary::Wrap => Boundary::Solid,
+        };
+        self.session.set_boundary(boundary);
+    }
+
+    pub fn speed_curve(&self) -> &SpeedCurve {
+        &self.speed_curve
+    }
+
+    pub fn speed_curve_mut(&mut self) -> &mut SpeedCurve {
+        &mut self.speed_curve
+    }
+
+    pub fn next_difficulty(&mut self) {
+        self.speed_curve = Difficulty::next(&self.speed_curve).curve();
+    }
+
+    pub fn autopilot(&self) -> bool {
+        self.autopilot
+    }
+
+    pub fn set_autopilot(&mut self, autopilot: bool) {
+        self.autopilot = autopilot;
+        self.controller = self.strategy.controller();
+    }
+
+    pub fn toggle_autopilot(&mut self) {
+        self.set_autopilot(!self.autopilot);
+    }
+
+    pub fn strategy(&self) -> Strategy {
+        self.strategy
+    }
+
+    pub fn set_strategy(&mut self, strategy: Strategy) {
+        self.strategy = strategy;
+        self.controller = strategy.controller();
+    }
+
+    pub fn next_strategy(&mut self) {
+        let index = Strategy::ALL.iter().position(|strategy| *strategy == self.strategy).unwrap_or(0);
+        self.set_strategy(Strategy::ALL[(index + 1) % Strategy::ALL.len()]);
+    }
+
+    // Scores the bot helped with don't go on the high score table
+    pub fn bot_played(&self) -> bool {
+        self.bot_played
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_bot_plays_until_restarted() {
+        let mut driver = Driver::new(Level::bundled(), 3);
+        driver.toggle_autopilot();
+        driver.session_mut().start();
+        for _ in 0..60 {
+            driver.step(1. / 30.);
+        }
+        assert!(driver.session().game().ticks() > 0);
+        assert!(driver.bot_played());
+
+        driver.restart();
+        assert_eq!(driver.session().game().ticks(), 0);
+        assert!(!driver.bot_played());
+        assert_eq!(driver.play_time(), 0.);
+    }
+
+    #[test]
+    fn only_plays_while_playing() {
+        let mut driver = Driver::new(Level::bundled(), 3);
+        driver.step(1.);
+        assert_eq!(driver.session().game().ticks(), 0);
+        driver.session_mut().start();
+        driver.session_mut().toggle_pause();
+        driver.step(1.);
+        assert_eq!(driver.session().game().ticks(), 0);
+        assert_eq!(driver.play_time(), 0.);
+    }
+
+    #[test]
+    fn levels_wrap_around() {
+        let mut driver = Driver::new(Level::bundled(), 3);
+        let count = driver.levels().len() as isize;
+        driver.change_level(-1);
+        assert_eq!(driver.level().name, driver.levels()[count as usize - 1].name);
+        driver.change_level(count + 1);
+        assert_eq!(driver.level().name, driver.levels()[0].name);
+        assert_eq!(driver.session().config().level.name, driver.levels()[0].name);
+    }
+}
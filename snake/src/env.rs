@@ -0,0 +1,258 @@
+// Gym style wrapper around `Game` for training agents: `reset` starts a game, `step` does one action and says how
+// good it was. Nothing in here touches nannou, so it runs as fast as the game can tick
+
+use crate::ai::distance;
+use crate::game::{Config, Game, Outcome};
+use crate::grid::{Boundary, Cell, Direction};
+
+// Actions are relative to where the snake is going, so there's no action that turns it back into itself
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Action {
+    Straight,
+    Left,
+    Right,
+}
+
+impl Action {
+    pub const ALL: [Action; 3] = [Action::Straight, Action::Left, Action::Right];
+
+    // For agents that pick an action by index, like the output of a network. None if it's out of range
+    pub fn from_index(index: usize) -> Option<Action> {
+        Action::ALL.get(index).copied()
+    }
+
+    fn apply(self, direction: Direction) -> Direction {
+        match self {
+            Action::Straight => direction,
+            Action::Left => direction.left(),
+            Action::Right => direction.right(),
+        }
+    }
+}
+
+// How the board is turned into numbers for the agent
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Encoding {
+    // One layer per kind of thing (walls, body, head, food), 1 where it is and 0 everywhere else. Shape is
+    // [4, height, width], rows go from the bottom of the board up
+    Grid,
+    // 8 rays from the head (ahead, ahead right, right, ... turning clockwise, relative to the snake) with 1 / distance
+    // to the first wall, body segment and food on each, 0 if the ray doesn't hit one. Shape is [8, 3]
+    Rays,
+}
+
+const GRID_LAYERS: usize = 4;
+const RAYS: usize = 8;
+const RAY_VALUES: usize = 3;
+
+// Numbers the agent gets after every step, `data` is `shape` flattened with the last dimension changing fastest
+#[derive(Clone, Debug, PartialEq)]
+pub struct Observation {
+    pub shape: Vec<usize>,
+    pub data: Vec<f32>,
+}
+
+// How much every kind of thing that can happen in a step is worth
+#[derive(Copy, Clone, Debug)]
+pub struct Rewards {
+    pub food: f32,
+    pub death: f32,
+    pub win: f32,
+    pub step: f32,   // Usually a bit below 0, so wandering around isn't free
+    pub closer: f32, // Shaping: given for getting closer to the closest food, taken away for going further
+}
+
+impl Default for Rewards {
+    fn default() -> Rewards {
+        Rewards { food: 1., death: -1., win: 10., step: -0.01, closer: 0.01 }
+    }
+}
+
+#[derive(Clone)]
+pub struct EnvConfig {
+    pub game: Config,
+    pub encoding: Encoding,
+    pub rewards: Rewards,
+    // The episode ends (without the death penalty) after this many steps without eating, so agents that learned to
+    // go in circles don't run forever
+    pub max_steps_without_food: u32,
+}
+
+impl Default for EnvConfig {
+    fn default() -> EnvConfig {
+        let game = Config::default();
+        let max_steps_without_food = game.level.grid.area() as u32;
+        EnvConfig { game, encoding: Encoding::Grid, rewards: Rewards::default(), max_steps_without_food }
+    }
+}
+
+pub struct SnakeEnv {
+    config: EnvConfig,
+    game: Game,
+    steps_without_food: u32,
+    done: bool,
+}
+
+// What's on a cell, for building observations
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Tile {
+    Empty,
+    Wall,
+    Body,
+    Food,
+}
+
+impl SnakeEnv {
+    pub fn new(config: EnvConfig) -> SnakeEnv {
+        let game = Game::new(&config.game, 0);
+        SnakeEnv { config, game, steps_without_food: 0, done: false }
+    }
+
+    // Starts a new episode, the same seed always gives the same food
+    pub fn reset(&mut self, seed: u64) -> Observation {
+        self.game = Game::new(&self.config.game, seed);
+        self.steps_without_food = 0;
+        self.done = false;
+        self.observe()
+    }
+
+    // Moves the snake one cell. Stepping after the episode is done does nothing and gives no reward
+    pub fn step(&mut self, action: Action) -> (Observation, f32, bool) {
+        if self.done {
+            return (self.observe(), 0., true);
+        }
+
+        let rewards = self.config.rewards;
+        let eaten = self.game.eaten();
+        let distance_before = self.food_distance();
+
+        self.game.turn(action.apply(self.game.snake().direction()));
+        self.game.tick();
+
+        let mut reward = rewards.step;
+        match self.game.outcome() {
+            Some(Outcome::Lost) => reward += rewards.death,
+            Some(Outcome::Won) => reward += rewards.win,
+            _ => {}
+        }
+        if self.game.eaten() > eaten {
+            reward += rewards.food;
+            self.steps_without_food = 0;
+        } else if let (Some(before), Some(after)) = (distance_before, self.food_distance()) {
+            reward += rewards.closer * (before - after).signum() as f32;
+            self.steps_without_food += 1;
+        } else {
+            self.steps_without_food += 1;
+        }
+
+        self.done = self.game.is_over() || self.steps_without_food >= self.config.max_steps_without_food;
+        (self.observe(), reward, self.done)
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    pub fn observation_shape(&self) -> Vec<usize> {
+        let grid = self.game.grid();
+        match self.config.encoding {
+            Encoding::Grid => vec![GRID_LAYERS, grid.height as usize, grid.width as usize],
+            Encoding::Rays => vec![RAYS, RAY_VALUES],
+        }
+    }
+
+    pub fn observe(&self) -> Observation {
+        let tiles = self.tiles();
+        let data = match self.config.encoding {
+            Encoding::Grid => self.grid_data(&tiles),
+            Encoding::Rays => self.ray_data(&tiles),
+        };
+        Observation { shape: self.observation_shape(), data }
+    }
+
+    // Steps from the head to the closest food, ignoring what's in the way
+    fn food_distance(&self) -> Option<i32> {
+        let head = self.game.snake().head();
+        self.game.food().iter().map(|food| distance(&self.game, head, food.cell)).min()
+    }
+
+    fn index(&self, cell: Cell) -> usize {
+        (cell.y * self.game.grid().width + cell.x) as usize
+    }
+
+    fn tiles(&self) -> Vec<Tile> {
+        let mut tiles = vec![Tile::Empty; self.game.grid().area()];
+        for wall in &self.game.level().walls {
+            tiles[self.index(*wall)] = Tile::Wall;
+        }
+        for food in self.game.food() {
+            tiles[self.index(food.cell)] = Tile::Food;
+        }
+        for segment in self.game.snake().body() {
+            tiles[self.index(segment)] = Tile::Body;
+        }
+        tiles
+    }
+
+    fn grid_data(&self, tiles: &[Tile]) -> Vec<f32> {
+        let area = tiles.len();
+        let mut data = vec![0.; GRID_LAYERS * area];
+        for (i, tile) in tiles.iter().enumerate() {
+            match tile {
+                Tile::Wall => data[i] = 1.,
+                Tile::Body => data[area + i] = 1.,
+                Tile::Food => data[3 * area + i] = 1.,
+                Tile::Empty => {}
+            }
+        }
+        data[2 * area + self.index(self.game.snake().head())] = 1.;
+        data
+    }
+
+    fn ray_data(&self, tiles: &[Tile]) -> Vec<f32> {
+        let grid = self.game.grid();
+        let snake = self.game.snake();
+        let head = snake.head();
+        // Ahead and to the right of the snake as vectors, every ray is a mix of the two
+        let ahead = Cell::new(0, 0).step(snake.direction());
+        let right = Cell::new(0, 0).step(snake.direction().right());
+        let rays = [(1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1), (0, -1), (1, -1)];
+        // In wrap mode a ray could go on forever, it stops once it's been all the way around
+        let max_length = grid.width.max(grid.height);
+
+        let mut data = Vec::with_capacity(RAYS * RAY_VALUES);
+        for (forward, sideways) in rays {
+            let step = (ahead.x * forward + right.x * sideways, ahead.y * forward + right.y * sideways);
+            let (mut wall, mut body, mut food) = (0., 0., 0.);
+            let mut cell = head;
+            for distance in 1..=max_length {
+                cell = Cell::new(cell.x + step.0, cell.y + step.1);
+                if !grid.contains(cell) {
+                    match self.game.boundary() {
+                        Boundary::Solid => {
+                            wall = 1. / distance as f32;
+                            break;
+                        }
+                        Boundary::Wrap => cell = grid.wrap(cell),
+                    }
+                }
+                let value = 1. / distance as f32;
+                match tiles[self.index(cell)] {
+                    Tile::Wall => {
+                        wall = value;
+                        break;
+                    }
+                    Tile::Body if body == 0. => body = value,
+                    Tile::Food if food == 0. => food = value,
+                    _ => {}
+                }
+            }
+            data.extend([wall, body, food]);
+        }
+        data
+    }
+}
@@ -0,0 +1,112 @@
+// The different kinds of food and what they do to whoever eats them
+
+use rand::Rng;
+use support::SimRng;
+
+use crate::game::FOOD_POINTS;
+use crate::grid::Cell;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum FoodKind {
+    Normal, // One segment, the only kind there used to be
+    Big,    // A few segments and more points
+    Fast,   // Speeds the game up for a while
+    Slow,   // Slows the game down for a while
+    Shrink, // Takes segments off the tail
+    Double, // Double points for a while, only for whoever ate it
+}
+
+// How often every kind of food spawns compared to the others, by `FoodKind::index`
+pub type Weights = [u32; FoodKind::ALL.len()];
+
+// Only normal food, the way the game used to be. Recordings from before there were other kinds play with this
+pub const CLASSIC_WEIGHTS: Weights = [1, 0, 0, 0, 0, 0];
+pub const DEFAULT_WEIGHTS: Weights = [20, 3, 2, 2, 2, 2];
+
+// Segments a big one adds, and shrink takes off (the head always stays)
+const BIG_GROWTH: u32 = 3;
+pub const SHRINK_SEGMENTS: usize = 3;
+// How long the timed effects last, and what they do
+pub const EFFECT_TICKS: u32 = 60;
+pub const FAST_SPEED: f32 = 1.5;
+pub const SLOW_SPEED: f32 = 0.6;
+pub const DOUBLE_POINTS: u32 = 2;
+// Anything but normal food goes away again if nobody gets to it in time
+const LIFETIME: u32 = 80;
+
+impl FoodKind {
+    pub const ALL: [FoodKind; 6] =
+        [FoodKind::Normal, FoodKind::Big, FoodKind::Fast, FoodKind::Slow, FoodKind::Shrink, FoodKind::Double];
+
+    pub fn index(self) -> usize {
+        FoodKind::ALL.iter().position(|kind| *kind == self).unwrap_or(0)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            FoodKind::Normal => "Normal",
+            FoodKind::Big => "Big",
+            FoodKind::Fast => "Speed up",
+            FoodKind::Slow => "Slow down",
+            FoodKind::Shrink => "Shrink",
+            FoodKind::Double => "Double points",
+        }
+    }
+
+    // Segments it adds to the snake
+    pub fn growth(self) -> u32 {
+        match self {
+            FoodKind::Big => BIG_GROWTH,
+            FoodKind::Shrink => 0,
+            _ => 1,
+        }
+    }
+
+    // Points before the speed bonus and multipliers
+    pub fn points(self) -> u32 {
+        match self {
+            FoodKind::Big => 3 * FOOD_POINTS,
+            FoodKind::Shrink => FOOD_POINTS / 2,
+            _ => FOOD_POINTS,
+        }
+    }
+
+    // Ticks it stays on the board, None if it stays until it's eaten
+    pub fn lifetime(self) -> Option<u32> {
+        match self {
+            FoodKind::Normal => None,
+            _ => Some(LIFETIME),
+        }
+    }
+
+    // Picks a kind going by `weights`. With only one kind possible nothing is taken from the rng, so games with the
+    // classic weights play exactly like they did before there were other kinds
+    pub fn pick(weights: &Weights, rng: &mut SimRng) -> FoodKind {
+        let total: u32 = weights.iter().sum();
+        if weights.iter().filter(|weight| **weight > 0).count() <= 1 {
+            let only = weights.iter().position(|weight| *weight > 0).unwrap_or(0);
+            return FoodKind::ALL[only];
+        }
+        let mut roll = rng.gen_range(0..total);
+        for (kind, weight) in FoodKind::ALL.iter().zip(weights) {
+            if roll < *weight {
+                return *kind;
+            }
+            roll -= weight;
+        }
+        FoodKind::Normal
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Food {
+    pub cell: Cell,
+    pub kind: FoodKind,
+    pub ticks_left: Option<u32>, // Until it goes away, None if it doesn't
+}
+
+impl Food {
+    pub fn new(cell: Cell, kind: FoodKind) -> Food {
+        Food { cell, kind, ticks_left: kind.lifetime() }
+    }
+}
@@ -0,0 +1,497 @@
+use std::collections::VecDeque;
+
+use rand::seq::IteratorRandom;
+use support::SimRng;
+
+use crate::food::{self, Food, FoodKind, Weights};
+use crate::grid::{Boundary, Cell, Direction, Grid};
+use crate::input::DirectionQueue;
+use crate::level::Level;
+use crate::replay::{Event, EventKind};
+
+// Stores a player's snake and how they're doing, the head is the first segment
+pub struct Snake {
+    body: VecDeque<Cell>,
+    prev_body: Vec<Cell>, // Body before the last tick, so drawing can go smoothly from one to the other
+    direction: Direction,
+    growth: u32, // Segments that still have to be added, they come out of the tail one per tick
+    input: DirectionQueue, // Turns that haven't happened yet
+    alive: bool,
+    eaten: u32, // Food eaten so far
+    score: u32,
+    ticks_since_food: u32, // For the speed bonus
+    double_ticks: u32,     // Ticks left with double points
+}
+
+impl Snake {
+    pub fn new(head: Cell, direction: Direction) -> Snake {
+        Snake {
+            body: VecDeque::from(vec![head]),
+            prev_body: vec![head],
+            direction,
+            growth: 0,
+            input: DirectionQueue::new(direction),
+            alive: true,
+            eaten: 0,
+            score: 0,
+            ticks_since_food: 0,
+            double_ticks: 0,
+        }
+    }
+
+    pub fn head(&self) -> Cell {
+        self.body[0]
+    }
+
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    pub fn tail(&self) -> Cell {
+        self.body[self.body.len() - 1]
+    }
+
+    pub fn length(&self) -> usize {
+        self.body.len()
+    }
+
+    // Segments still to be added, while there are any the tail stays where it is
+    pub fn growth(&self) -> u32 {
+        self.growth
+    }
+
+    // Segments from the head to the tail
+    pub fn body(&self) -> impl Iterator<Item = Cell> + '_ {
+        self.body.iter().copied()
+    }
+
+    // Where segment `index` was before the last tick. New segments weren't anywhere yet, so they stay in place
+    pub fn previous(&self, index: usize) -> Cell {
+        self.prev_body.get(index).copied().unwrap_or(self.body[index])
+    }
+
+    pub fn contains(&self, cell: Cell) -> bool {
+        self.body.contains(&cell)
+    }
+
+    // Dead snakes stay where they died but don't take part anymore, with more than one player they also stop
+    // being in the way
+    pub fn is_alive(&self) -> bool {
+        self.alive
+    }
+
+    pub fn eaten(&self) -> u32 {
+        self.eaten
+    }
+
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    // Ticks since it last ate, 0 right after eating
+    pub fn ticks_since_food(&self) -> u32 {
+        self.ticks_since_food
+    }
+
+    // Ticks left with double points, 0 if there's no double food in effect
+    pub fn double_ticks(&self) -> u32 {
+        self.double_ticks
+    }
+
+    // If `cell` is on the body, not counting the head
+    fn body_contains(&self, cell: Cell) -> bool {
+        self.body.iter().skip(1).any(|segment| *segment == cell)
+    }
+
+    // Moves the head to `head`, the tail follows unless the snake is still growing
+    fn update_position(&mut self, head: Cell) {
+        self.prev_body.clear();
+        self.prev_body.extend(self.body.iter().copied());
+
+        self.body.push_front(head);
+        if self.growth > 0 {
+            self.growth -= 1;
+        } else {
+            self.body.pop_back();
+        }
+    }
+
+    // Points and whatever else the food does to the snake itself, effects on the whole game are up to `Game::tick`
+    fn eat(&mut self, kind: FoodKind) {
+        let points = kind.points() + MAX_SPEED_BONUS.saturating_sub(self.ticks_since_food);
+        self.score += if self.double_ticks > 0 { points * food::DOUBLE_POINTS } else { points };
+        self.eaten += 1;
+        self.ticks_since_food = 0;
+        self.growth += kind.growth();
+        match kind {
+            FoodKind::Shrink => {
+                let keep = self.body.len().saturating_sub(food::SHRINK_SEGMENTS).max(1);
+                self.body.truncate(keep);
+                self.growth = 0;
+            }
+            FoodKind::Double => self.double_ticks = food::EFFECT_TICKS,
+            _ => {}
+        }
+    }
+}
+
+// Points for every piece of normal food eaten
+pub const FOOD_POINTS: u32 = 10;
+// Extra points for getting to food quickly, one less for every tick it took since the last one was eaten
+pub const MAX_SPEED_BONUS: u32 = 20;
+
+// More snakes than this don't fit on a board anyone wants to play on
+pub const MAX_PLAYERS: usize = 16;
+
+// Everything about a game that is decided before it starts
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub level: Level,
+    pub boundary: Boundary,
+    pub food_count: usize,     // How much food is on the board at the same time
+    pub players: usize,        // Every player gets their own snake, the food is shared
+    pub food_weights: Weights, // How often every kind of food spawns
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            level: Level::open(Grid::new(40, 40)),
+            boundary: Boundary::Solid,
+            food_count: 1,
+            players: 1,
+            food_weights: food::DEFAULT_WEIGHTS,
+        }
+    }
+}
+
+impl Config {
+    // If every player gets a snake, small levels can run out of places to start
+    pub fn has_room(&self) -> bool {
+        let players = self.players.max(1);
+        spawns(&self.level, players).len() == players
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Lost, // Hit a wall, the edge or itself
+    Won,  // Filled the whole board
+    // The ones below are only for games with more than one player
+    Winner(usize), // Last one alive, or the best score if the board got full
+    Draw,          // The last ones died on the same tick, or the board got full with a tie
+}
+
+// Where every player starts. The first one is on the level's spawn, the others are mirrored from it so nobody has a
+// better start. If the mirrored spot is a wall (or taken) they go to the first place that's free
+fn spawns(level: &Level, players: usize) -> Vec<(Cell, Direction)> {
+    let grid = level.grid;
+    let spawn = level.spawn;
+    let flip_x = Cell::new(grid.width - 1 - spawn.x, spawn.y);
+    let flip_y = Cell::new(spawn.x, grid.height - 1 - spawn.y);
+    let flip_horizontal = |direction: Direction| match direction {
+        Direction::Left | Direction::Right => direction.opposite(),
+        _ => direction,
+    };
+    let mirrored = [
+        (Cell::new(flip_x.x, flip_y.y), level.direction.opposite()),
+        (flip_x, flip_horizontal(level.direction)),
+        (flip_y, flip_horizontal(level.direction).opposite()),
+    ];
+
+    let mut spawns = vec![(spawn, level.direction)];
+    let is_free = |spawns: &[(Cell, Direction)], (cell, direction): (Cell, Direction)| {
+        let first_step = cell.step(direction);
+        let taken = |cell: Cell| level.is_wall(cell) || spawns.iter().any(|(spawn, _)| *spawn == cell);
+        grid.contains(first_step) && !taken(cell) && !taken(first_step)
+    };
+    for player in 1..players {
+        let candidate = mirrored.get(player - 1).copied().filter(|candidate| is_free(&spawns, *candidate));
+        let spawn = candidate.or_else(|| {
+            grid.cells()
+                .flat_map(|cell| Direction::ALL.into_iter().map(move |direction| (cell, direction)))
+                .find(|candidate| is_free(&spawns, *candidate))
+        });
+        // Small levels can run out of room, then there are fewer snakes than players (see `Config::has_room`)
+        match spawn {
+            Some(spawn) => spawns.push(spawn),
+            None => break,
+        }
+    }
+    spawns
+}
+
+// The whole game without anything nannou, it only moves forward when `tick` gets called
+pub struct Game {
+    config: Config, // What it was started with, the food count can change while playing
+    seed: u64,
+    snakes: Vec<Snake>, // One per player
+    food: Vec<Food>,
+    food_count: usize,
+    speed_change: Option<(FoodKind, u32)>, // Fast or slow food that's in effect, and for how many more ticks
+    outcome: Option<Outcome>, // Set once the game is over
+    rng: SimRng, // Decides where the food goes
+    ticks: u32,
+    events: Vec<Event>, // Everything the players did, with the seed that's enough to play the game again
+}
+
+impl Game {
+    pub fn new(config: &Config, seed: u64) -> Game {
+        let snakes = spawns(&config.level, config.players.max(1))
+            .into_iter()
+            .map(|(head, direction)| Snake::new(head, direction))
+            .collect();
+        // More food than fits plays the same as the most that fits, replays and the network only accept that much
+        let mut config = config.clone();
+        config.food_count = config.food_count.min(config.level.max_food());
+        let mut game = Game {
+            seed,
+            snakes,
+            food: Vec::new(),
+            food_count: config.food_count,
+            config,
+            speed_change: None,
+            outcome: None,
+            rng: support::seeded_rng(seed),
+            ticks: 0,
+            events: Vec::new(),
+        };
+        game.fill_food();
+        game
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn grid(&self) -> Grid {
+        self.config.level.grid
+    }
+
+    pub fn level(&self) -> &Level {
+        &self.config.level
+    }
+
+    pub fn boundary(&self) -> Boundary {
+        self.config.boundary
+    }
+
+    // Ticks played so far
+    pub fn ticks(&self) -> u32 {
+        self.ticks
+    }
+
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    // The first player's snake, the only one in a single player game
+    pub fn snake(&self) -> &Snake {
+        &self.snakes[0]
+    }
+
+    pub fn snakes(&self) -> &[Snake] {
+        &self.snakes
+    }
+
+    pub fn food(&self) -> &[Food] {
+        &self.food
+    }
+
+    // How much faster than normal the game should run, front-ends multiply their tick rate with this
+    pub fn speed(&self) -> f32 {
+        match self.speed_change {
+            Some((FoodKind::Fast, _)) => food::FAST_SPEED,
+            Some((FoodKind::Slow, _)) => food::SLOW_SPEED,
+            _ => 1.,
+        }
+    }
+
+    pub fn speed_change(&self) -> Option<(FoodKind, u32)> {
+        self.speed_change
+    }
+
+    // The first player's score
+    pub fn score(&self) -> u32 {
+        self.snake().score
+    }
+
+    pub fn eaten(&self) -> u32 {
+        self.snake().eaten
+    }
+
+    pub fn outcome(&self) -> Option<Outcome> {
+        self.outcome
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.outcome.is_some()
+    }
+
+    pub fn food_count(&self) -> usize {
+        self.food_count
+    }
+
+    // Changes how much food is on the board, extra food gets removed right away and missing food spawned
+    pub fn set_food_count(&mut self, food_count: usize) {
+        self.events.push(Event { tick: self.ticks, kind: EventKind::FoodCount(food_count) });
+        self.food_count = food_count;
+        self.food.truncate(food_count);
+        self.fill_food();
+    }
+
+    // Queues a turn for the first player
+    pub fn turn(&mut self, direction: Direction) -> bool {
+        self.turn_player(0, direction)
+    }
+
+    // Queues a turn for the next ticks, see `DirectionQueue::push`
+    pub fn turn_player(&mut self, player: usize, direction: Direction) -> bool {
+        let accepted = match self.snakes.get_mut(player) {
+            Some(snake) => snake.input.push(direction),
+            None => false,
+        };
+        // Ignored turns don't change anything, so they don't need to be kept
+        if accepted {
+            self.events.push(Event { tick: self.ticks, kind: EventKind::Turn(player, direction) });
+        }
+        accepted
+    }
+
+    // Random cell on the board that has no wall, snake or food on it, None if there aren't any left
+    fn random_free_cell(&mut self) -> Option<Cell> {
+        let snakes = &self.snakes;
+        let food = &self.food;
+        let level = &self.config.level;
+        level
+            .grid
+            .cells()
+            .filter(|cell| {
+                !level.is_wall(*cell)
+                    && !snakes.iter().any(|snake| snake.alive && snake.contains(*cell))
+                    && !food.iter().any(|food| food.cell == *cell)
+            })
+            .choose(&mut self.rng)
+    }
+
+    // Spawns food until there's `food_count` of it, or until there's no room left for more
+    fn fill_food(&mut self) {
+        while self.food.len() < self.food_count {
+            match self.random_free_cell() {
+                Some(cell) => {
+                    let kind = FoodKind::pick(&self.config.food_weights, &mut self.rng);
+                    self.food.push(Food::new(cell, kind));
+                }
+                None => break,
+            }
+        }
+    }
+
+    // If the head of snake `index` ran into anything. Called after every snake moved, so it doesn't matter who's
+    // first in the list
+    fn has_crashed(&self, index: usize) -> bool {
+        let snake = &self.snakes[index];
+        let head = snake.head();
+        if self.level().is_wall(head) || snake.body_contains(head) {
+            return true;
+        }
+        self.snakes.iter().enumerate().filter(|(other, snake)| *other != index && snake.alive).any(|(_, other)| {
+            // Running into the other one, head on or from the side. Two snakes going through each other head first
+            // swap cells without landing on the same one, that counts as head on too
+            let swapped = other.head() == snake.previous(0) && head == other.previous(0);
+            other.head() == head || other.body_contains(head) || swapped
+        })
+    }
+
+    // One step of the game itself
+    pub fn tick(&mut self) {
+        // If the game is over, we shouldn't continue to do calculations in the "backend"
+        if self.is_over() {
+            return;
+        }
+        self.ticks += 1;
+
+        // Everyone moves first
+        let (grid, boundary) = (self.grid(), self.boundary());
+        for snake in self.snakes.iter_mut().filter(|snake| snake.alive) {
+            if let Some(direction) = snake.input.pop() {
+                snake.direction = direction;
+            }
+            match grid.neighbor(snake.head(), snake.direction, boundary) {
+                Some(head) => {
+                    snake.update_position(head);
+                    snake.ticks_since_food += 1;
+                }
+                // Off the edge, it stays where it was so it's still on the board
+                None => snake.alive = false,
+            }
+        }
+
+        // Then it gets checked who ran into what
+        let crashed: Vec<usize> =
+            (0..self.snakes.len()).filter(|index| self.snakes[*index].alive && self.has_crashed(*index)).collect();
+        for index in crashed {
+            self.snakes[index].alive = false;
+        }
+
+        // Effects run out before anything new gets eaten, so eating the same kind again starts it over
+        for snake in &mut self.snakes {
+            snake.double_ticks = snake.double_ticks.saturating_sub(1);
+        }
+        self.speed_change = self.speed_change.and_then(|(kind, ticks)| (ticks > 1).then_some((kind, ticks - 1)));
+
+        let food_before = self.food.len();
+        for snake in self.snakes.iter_mut().filter(|snake| snake.alive) {
+            let head = snake.head();
+            if let Some(index) = self.food.iter().position(|food| food.cell == head) {
+                let food = self.food.swap_remove(index);
+                snake.eat(food.kind);
+                if let FoodKind::Fast | FoodKind::Slow = food.kind {
+                    self.speed_change = Some((food.kind, food::EFFECT_TICKS));
+                }
+            }
+        }
+        // Food that's been around too long goes away, new food takes its place
+        for food in &mut self.food {
+            food.ticks_left = food.ticks_left.map(|ticks| ticks.saturating_sub(1));
+        }
+        self.food.retain(|food| food.ticks_left != Some(0));
+        if self.food.len() < food_before {
+            self.fill_food();
+        }
+
+        self.outcome = self.check_outcome();
+    }
+
+    fn check_outcome(&self) -> Option<Outcome> {
+        let alive: Vec<usize> = (0..self.snakes.len()).filter(|index| self.snakes[*index].alive).collect();
+        let length: usize = alive.iter().map(|index| self.snakes[*index].length()).sum();
+        let board_full = length == self.level().free_area();
+
+        if self.snakes.len() == 1 {
+            return match (alive.is_empty(), board_full) {
+                (true, _) => Some(Outcome::Lost),
+                (false, true) => Some(Outcome::Won),
+                (false, false) => None,
+            };
+        }
+        match alive.len() {
+            0 => Some(Outcome::Draw),
+            1 => Some(Outcome::Winner(alive[0])),
+            _ if board_full => {
+                let best = alive.iter().map(|index| self.snakes[*index].score).max().unwrap_or(0);
+                let best_players: Vec<usize> =
+                    alive.into_iter().filter(|index| self.snakes[*index].score == best).collect();
+                match best_players[..] {
+                    [winner] => Some(Outcome::Winner(winner)),
+                    _ => Some(Outcome::Draw),
+                }
+            }
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,111 @@
+// The board is a grid of square cells. (0, 0) is the bottom left cell, x goes right and y goes up like in nannou
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Cell {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Cell {
+    pub fn new(x: i32, y: i32) -> Cell {
+        Cell { x, y }
+    }
+
+    // The cell next to this one in `direction`, may be outside the grid
+    pub fn step(self, direction: Direction) -> Cell {
+        match direction {
+            Direction::Up => Cell::new(self.x, self.y + 1),
+            Direction::Down => Cell::new(self.x, self.y - 1),
+            Direction::Left => Cell::new(self.x - 1, self.y),
+            Direction::Right => Cell::new(self.x + 1, self.y),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [Direction::Up, Direction::Down, Direction::Left, Direction::Right];
+
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+
+    // Direction after turning 90 degrees to the left, from the snake's point of view
+    pub fn left(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Left => Direction::Down,
+            Direction::Down => Direction::Right,
+            Direction::Right => Direction::Up,
+        }
+    }
+
+    pub fn right(self) -> Direction {
+        self.left().opposite()
+    }
+}
+
+// What happens when the snake goes off the side of the board
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Boundary {
+    Solid, // The edge is a wall
+    Wrap,  // It comes back in on the opposite side
+}
+
+// Size of the board in cells
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Grid {
+    pub width: i32,
+    pub height: i32,
+}
+
+impl Grid {
+    pub fn new(width: i32, height: i32) -> Grid {
+        Grid { width, height }
+    }
+
+    pub fn contains(&self, cell: Cell) -> bool {
+        cell.x >= 0 && cell.x < self.width && cell.y >= 0 && cell.y < self.height
+    }
+
+    pub fn center(&self) -> Cell {
+        Cell::new(self.width / 2, self.height / 2)
+    }
+
+    // Brings a cell that's off the board back onto it from the other side
+    pub fn wrap(&self, cell: Cell) -> Cell {
+        Cell::new(cell.x.rem_euclid(self.width), cell.y.rem_euclid(self.height))
+    }
+
+    // The cell next to `cell` in `direction`, None if that's off the board and the edge is solid
+    pub fn neighbor(&self, cell: Cell, direction: Direction, boundary: Boundary) -> Option<Cell> {
+        let next = cell.step(direction);
+        match boundary {
+            Boundary::Solid => Some(next).filter(|next| self.contains(*next)),
+            Boundary::Wrap => Some(self.wrap(next)),
+        }
+    }
+
+    // Amount of cells on the board
+    pub fn area(&self) -> usize {
+        (self.width * self.height) as usize
+    }
+
+    // Every cell on the board, row by row from the bottom
+    pub fn cells(&self) -> impl Iterator<Item = Cell> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Cell::new(x, y)))
+    }
+}
@@ -0,0 +1,141 @@
+use std::cmp::Reverse;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+// Bump this when the file layout changes, and teach `HighScores::load` to read the old one
+const VERSION: u32 = 1;
+// Only the best ones are kept
+const MAX_ENTRIES: usize = 10;
+const MAX_NAME_LENGTH: usize = 12;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    pub name: String,
+    pub score: u32,
+    pub length: usize,
+}
+
+// What actually gets written to the file
+#[derive(Serialize, Deserialize)]
+struct File {
+    version: u32,
+    entries: Vec<Entry>,
+}
+
+// Read first so we know how to read the rest
+#[derive(Deserialize)]
+struct FileVersion {
+    version: u32,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Parse(serde_json::Error),
+    // Saving went wrong, the table itself is fine
+    Write(io::Error),
+    // The file was written by a newer version of the game
+    UnsupportedVersion(u32),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "couldn't read the high score file: {}", err),
+            Error::Parse(err) => write!(f, "the high score file is broken: {}", err),
+            Error::Write(err) => write!(f, "couldn't save the high score file: {}", err),
+            Error::UnsupportedVersion(version) => {
+                write!(f, "the high score file has version {}, this game only knows up to {}", version, VERSION)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+// The high score table, best score first
+#[derive(Clone, Debug, Default)]
+pub struct HighScores {
+    entries: Vec<Entry>,
+}
+
+impl HighScores {
+    // Where the table is kept, somewhere in the user's data dir (~/.local/share on linux)
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("nannou-projects").join("snake_highscores.json"))
+    }
+
+    // A file that doesn't exist yet is just an empty table
+    pub fn load(path: &Path) -> Result<HighScores, Error> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(HighScores::default()),
+            Err(err) => return Err(Error::Io(err)),
+        };
+        let version = serde_json::from_str::<FileVersion>(&text).map_err(Error::Parse)?.version;
+        if version != VERSION {
+            return Err(Error::UnsupportedVersion(version));
+        }
+        let file: File = serde_json::from_str(&text).map_err(Error::Parse)?;
+
+        let mut high_scores = HighScores { entries: file.entries };
+        high_scores.sort();
+        Ok(high_scores)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(Error::Write)?;
+        }
+        let file = File { version: VERSION, entries: self.entries.clone() };
+        let text = serde_json::to_string_pretty(&file).map_err(|err| Error::Write(err.into()))?;
+        fs::write(path, text).map_err(Error::Write)
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    // If the score is good enough to make it onto the table
+    pub fn qualifies(&self, score: u32) -> bool {
+        score > 0 && (self.entries.len() < MAX_ENTRIES || self.entries.iter().any(|entry| score > entry.score))
+    }
+
+    // Adds the entry if it qualifies and returns its place on the table (0 is the best)
+    pub fn insert(&mut self, mut entry: Entry) -> Option<usize> {
+        if !self.qualifies(entry.score) {
+            return None;
+        }
+        entry.name = clean_name(&entry.name);
+        // Equal scores go below the ones that were there first
+        let rank = self.entries.iter().position(|other| entry.score > other.score).unwrap_or(self.entries.len());
+        self.entries.insert(rank, entry);
+        self.entries.truncate(MAX_ENTRIES);
+        Some(rank)
+    }
+
+    fn sort(&mut self) {
+        self.entries.sort_by_key(|entry| Reverse(entry.score));
+        self.entries.truncate(MAX_ENTRIES);
+    }
+}
+
+// Names are short and printable, empty ones become "???"
+pub fn clean_name(name: &str) -> String {
+    let name: String = name.chars().filter(|c| !c.is_control()).take(MAX_NAME_LENGTH).collect();
+    let name = name.trim();
+    if name.is_empty() {
+        "???".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+// If another character can still be typed into a name
+pub fn name_has_room(name: &str) -> bool {
+    name.chars().count() < MAX_NAME_LENGTH
+}
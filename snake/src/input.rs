@@ -0,0 +1,78 @@
+use std::collections::VecDeque;
+
+use crate::grid::Direction;
+
+// Turns pressed between two ticks wait in here, so pressing Up then Left quickly does both turns one tick after
+// the other instead of the second one overwriting the first
+pub struct DirectionQueue {
+    queue: VecDeque<Direction>,
+    last: Direction, // Last accepted direction, the one new presses get checked against
+}
+
+// Any more than this and the snake would still be turning long after the keys were let go
+const MAX_QUEUED: usize = 3;
+
+impl DirectionQueue {
+    pub fn new(current: Direction) -> DirectionQueue {
+        DirectionQueue { queue: VecDeque::new(), last: current }
+    }
+
+    // Queues a turn. Turns that would reverse the snake into itself (or that don't change anything) are ignored,
+    // returns if the direction was accepted
+    pub fn push(&mut self, direction: Direction) -> bool {
+        if direction == self.last || direction == self.last.opposite() || self.queue.len() >= MAX_QUEUED {
+            return false;
+        }
+        self.queue.push_back(direction);
+        self.last = direction;
+        true
+    }
+
+    // Next direction to take, called once per tick
+    pub fn pop(&mut self) -> Option<Direction> {
+        self.queue.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quick_turns_both_apply() {
+        let mut queue = DirectionQueue::new(Direction::Right);
+        assert!(queue.push(Direction::Up));
+        // Left is only a reversal of the direction before Up
+        assert!(queue.push(Direction::Left));
+        assert_eq!(queue.pop(), Some(Direction::Up));
+        assert_eq!(queue.pop(), Some(Direction::Left));
+        assert_eq!(queue.pop(), None);
+    }
+
+    #[test]
+    fn reversing_is_dropped() {
+        let mut queue = DirectionQueue::new(Direction::Right);
+        assert!(!queue.push(Direction::Left));
+        assert!(!queue.push(Direction::Right));
+        assert_eq!(queue.pop(), None);
+
+        // Also when it's a reversal of a queued turn
+        assert!(queue.push(Direction::Up));
+        assert!(!queue.push(Direction::Down));
+        assert_eq!(queue.pop(), Some(Direction::Up));
+        assert_eq!(queue.pop(), None);
+    }
+
+    #[test]
+    fn queue_is_capped() {
+        let mut queue = DirectionQueue::new(Direction::Right);
+        let turns = [Direction::Up, Direction::Left, Direction::Down, Direction::Right];
+        let accepted: Vec<bool> = turns.iter().map(|direction| queue.push(*direction)).collect();
+        assert_eq!(accepted, [true, true, true, false]);
+        assert_eq!(MAX_QUEUED, 3);
+
+        // Popping makes room again
+        assert_eq!(queue.pop(), Some(Direction::Up));
+        assert!(queue.push(Direction::Right));
+    }
+}
@@ -0,0 +1,34 @@
+use nannou::prelude::*;
+use support::Bounds;
+
+use crate::grid::Grid;
+
+// Where the grid ends up on screen. Cells are square and as big as fits in the window, with the board in the middle
+pub struct Layout {
+    grid: Grid,
+    cell_size: f32,
+}
+
+impl Layout {
+    pub fn new(grid: Grid, bounds: Bounds) -> Layout {
+        let cell_size = (bounds.width as f32 / grid.width as f32).min(bounds.height as f32 / grid.height as f32);
+        Layout { grid, cell_size }
+    }
+
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    // Size of the whole board on screen
+    pub fn board_size(&self) -> Vec2 {
+        vec2(self.grid.width as f32, self.grid.height as f32) * self.cell_size
+    }
+
+    // Center of the cell at (x, y). Takes floats so positions in between two cells (while interpolating) work too
+    pub fn to_screen(&self, x: f32, y: f32) -> Point2 {
+        pt2(
+            (x + 0.5 - self.grid.width as f32 / 2.) * self.cell_size,
+            (y + 0.5 - self.grid.height as f32 / 2.) * self.cell_size,
+        )
+    }
+}
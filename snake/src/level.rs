@@ -0,0 +1,368 @@
+// Levels are plain text files, a few `key: value` lines and then the map itself, top row first:
+//
+//   # Lines starting with # are comments (only before the map, in the map # is a wall)
+//   name: Box
+//   size: 30 30       <- optional, if it's there the map has to be this size
+//   direction: right  <- where the snake is heading at the start
+//
+//   map:
+//   ##########
+//   #..S.....#
+//   ##########
+//
+// `#` is a wall, `.` is empty and `S` is where the snake starts (empty too)
+
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::grid::{Cell, Direction, Grid};
+
+// Anything bigger would have cells smaller than a pixel
+pub const MAX_SIZE: i32 = 200;
+
+// Levels that come with the game, the open board is made in code
+const BUNDLED: [&str; 4] = [
+    include_str!("../levels/box.txt"),
+    include_str!("../levels/cross.txt"),
+    include_str!("../levels/rooms.txt"),
+    include_str!("../levels/pillars.txt"),
+];
+
+#[derive(Clone, Debug)]
+pub struct Level {
+    pub name: String,
+    pub grid: Grid,
+    pub walls: HashSet<Cell>,
+    pub spawn: Cell,
+    pub direction: Direction,
+}
+
+// What's wrong with a level file
+#[derive(Debug)]
+pub enum Problem {
+    NotKeyValue,
+    UnknownKey(String),
+    DuplicateKey(String),
+    BadDirection(String),
+    BadSize(String),
+    MissingMap,
+    EmptyRow,
+    UnknownCell(char, usize),
+    RowWidth { width: i32, expected: i32 },
+    TooBig,
+    SizeMismatch { size: Grid, map: Grid },
+    NoSpawn,
+    SeveralSpawns,
+    BlockedSpawn,
+    NoRoom,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Problem::NotKeyValue => write!(f, "expected `key: value` or `map:`"),
+            Problem::UnknownKey(key) => write!(f, "unknown key `{}`, expected name, size or direction", key),
+            Problem::DuplicateKey(key) => write!(f, "`{}` is set twice", key),
+            Problem::BadDirection(value) => {
+                write!(f, "`{}` isn't a direction, expected up, down, left or right", value)
+            }
+            Problem::BadSize(value) => {
+                write!(f, "`{}` isn't a size, expected the width and height like `30 20`", value)
+            }
+            Problem::MissingMap => write!(f, "the file ends before the map, it should start with a `map:` line"),
+            Problem::EmptyRow => write!(f, "empty row in the map"),
+            Problem::UnknownCell(c, column) => write!(f, "`{}` in column {}, expected `#`, `.` or `S`", c, column),
+            Problem::RowWidth { width, expected } => {
+                write!(f, "row is {} cells wide but the first row is {}", width, expected)
+            }
+            Problem::TooBig => write!(f, "the map can be at most {} by {} cells", MAX_SIZE, MAX_SIZE),
+            Problem::SizeMismatch { size, map } => {
+                write!(f, "size says {}x{} but the map is {}x{}", size.width, size.height, map.width, map.height)
+            }
+            Problem::NoSpawn => write!(f, "the map has no `S` for the snake to start on"),
+            Problem::SeveralSpawns => write!(f, "second `S` in the map, there can only be one"),
+            Problem::BlockedSpawn => write!(f, "the snake would run into a wall or the edge on the first tick"),
+            Problem::NoRoom => write!(f, "the map has no empty cell for food"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Parse { line: usize, problem: Problem }, // Lines start at 1 like in an editor
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "couldn't read the level: {}", err),
+            Error::Parse { line, problem } => write!(f, "line {}: {}", line, problem),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Error {
+        Error::Io(err)
+    }
+}
+
+fn parse_direction(value: &str) -> Option<Direction> {
+    match value.to_lowercase().as_str() {
+        "up" => Some(Direction::Up),
+        "down" => Some(Direction::Down),
+        "left" => Some(Direction::Left),
+        "right" => Some(Direction::Right),
+        _ => None,
+    }
+}
+
+fn parse_size(value: &str) -> Option<Grid> {
+    let mut parts = value.split_whitespace().map(|part| part.parse::<i32>());
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(Ok(width)), Some(Ok(height)), None) if width > 0 && height > 0 => Some(Grid::new(width, height)),
+        _ => None,
+    }
+}
+
+impl Level {
+    // Empty board with the snake in the middle, what the game had before levels
+    pub fn open(grid: Grid) -> Level {
+        let name = "Open".to_string();
+        Level { name, grid, walls: HashSet::new(), spawn: grid.center(), direction: Direction::Right }
+    }
+
+    // The open board first, then the ones from the `levels` dir
+    pub fn bundled() -> Vec<Level> {
+        let mut levels = vec![Level::open(Grid::new(40, 40))];
+        // These are checked in with the game, so a broken one is a bug
+        levels.extend(BUNDLED.iter().map(|text| Level::parse(text).expect("bundled level doesn't parse")));
+        levels
+    }
+
+    // Where players can put their own levels, somewhere in the user's data dir (~/.local/share on linux)
+    pub fn user_dir() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("nannou-projects").join("snake_levels"))
+    }
+
+    // Every `.txt` file in `dir`, sorted by name. A dir that doesn't exist just has no levels
+    pub fn load_dir(dir: &Path) -> Vec<(PathBuf, Result<Level, Error>)> {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(_) => return Vec::new(),
+        };
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|extension| extension == "txt"))
+            .collect();
+        paths.sort();
+        paths.into_iter().map(|path| (path.clone(), Level::load(&path))).collect()
+    }
+
+    // A bundled level going by its name in any case, otherwise the level file at `name`. For command line arguments
+    pub fn find(name: &str) -> Result<Level, Error> {
+        match Level::bundled().into_iter().find(|level| level.name.eq_ignore_ascii_case(name)) {
+            Some(level) => Ok(level),
+            None => Level::load(Path::new(name)),
+        }
+    }
+
+    // Levels without a name get the name of their file
+    pub fn load(path: &Path) -> Result<Level, Error> {
+        let mut level = Level::parse(&fs::read_to_string(path)?)?;
+        if level.name.is_empty() {
+            level.name = path.file_stem().map_or(String::new(), |stem| stem.to_string_lossy().into_owned());
+        }
+        Ok(level)
+    }
+
+    pub fn parse(text: &str) -> Result<Level, Error> {
+        let error = |line: usize, problem: Problem| Error::Parse { line: line + 1, problem };
+        let lines: Vec<&str> = text.lines().map(|line| line.trim_end()).collect();
+
+        // Header
+        let mut name = None;
+        let mut size = None;
+        let mut direction = None;
+        let mut map_start = None;
+        for (number, line) in lines.iter().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line.split_once(':').ok_or_else(|| error(number, Problem::NotKeyValue))?;
+            let (key, value) = (key.trim(), value.trim());
+            let already_set = match key {
+                "map" => {
+                    map_start = Some(number);
+                    break;
+                }
+                "name" => name.replace(value.to_string()).is_some(),
+                "size" => {
+                    let grid = parse_size(value).ok_or_else(|| error(number, Problem::BadSize(value.to_string())))?;
+                    size.replace(grid).is_some()
+                }
+                "direction" => {
+                    let parsed = parse_direction(value)
+                        .ok_or_else(|| error(number, Problem::BadDirection(value.to_string())))?;
+                    direction.replace(parsed).is_some()
+                }
+                _ => return Err(error(number, Problem::UnknownKey(key.to_string()))),
+            };
+            if already_set {
+                return Err(error(number, Problem::DuplicateKey(key.to_string())));
+            }
+        }
+        let map_start = map_start.ok_or_else(|| error(lines.len().saturating_sub(1), Problem::MissingMap))?;
+
+        // Map, blank lines at the end of the file don't count as rows
+        let mut rows = &lines[map_start + 1..];
+        while let Some((last, rest)) = rows.split_last() {
+            if !last.is_empty() {
+                break;
+            }
+            rows = rest;
+        }
+        let height = rows.len() as i32;
+        let width = rows.first().map_or(0, |row| row.chars().count() as i32);
+        if height == 0 {
+            return Err(error(map_start, Problem::NoSpawn));
+        }
+        if width > MAX_SIZE || height > MAX_SIZE {
+            return Err(error(map_start, Problem::TooBig));
+        }
+        let grid = Grid::new(width, height);
+        if let Some(size) = size {
+            if size != grid {
+                return Err(error(map_start, Problem::SizeMismatch { size, map: grid }));
+            }
+        }
+
+        let mut walls = HashSet::new();
+        let mut spawn = None;
+        for (index, row) in rows.iter().enumerate() {
+            let number = map_start + 1 + index;
+            if row.is_empty() {
+                return Err(error(number, Problem::EmptyRow));
+            }
+            let row_width = row.chars().count() as i32;
+            if row_width != width {
+                return Err(error(number, Problem::RowWidth { width: row_width, expected: width }));
+            }
+            // The first row is the top of the board
+            let y = height - 1 - index as i32;
+            for (x, c) in row.chars().enumerate() {
+                let cell = Cell::new(x as i32, y);
+                match c {
+                    '#' => {
+                        walls.insert(cell);
+                    }
+                    '.' => {}
+                    'S' => {
+                        if spawn.replace((cell, number)).is_some() {
+                            return Err(error(number, Problem::SeveralSpawns));
+                        }
+                    }
+                    _ => return Err(error(number, Problem::UnknownCell(c, x + 1))),
+                }
+            }
+        }
+
+        let (spawn, spawn_line) = spawn.ok_or_else(|| error(map_start, Problem::NoSpawn))?;
+        let direction = direction.unwrap_or(Direction::Right);
+        let first_step = spawn.step(direction);
+        if !grid.contains(first_step) || walls.contains(&first_step) {
+            return Err(error(spawn_line, Problem::BlockedSpawn));
+        }
+        // The snake's own cell doesn't count, food can't go there
+        if grid.area() - walls.len() < 2 {
+            return Err(error(map_start, Problem::NoRoom));
+        }
+
+        Ok(Level { name: name.unwrap_or_default(), grid, walls, spawn, direction })
+    }
+
+    pub fn is_wall(&self, cell: Cell) -> bool {
+        self.walls.contains(&cell)
+    }
+
+    // Cells the snake can be on, filling all of them wins the game
+    pub fn free_area(&self) -> usize {
+        self.grid.area() - self.walls.len()
+    }
+
+    // The most food there can be at once, at least one cell has the snake on it
+    pub fn max_food(&self) -> usize {
+        self.free_area().saturating_sub(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_error(text: &str) -> (usize, Problem) {
+        match Level::parse(text) {
+            Err(Error::Parse { line, problem }) => (line, problem),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_a_level() {
+        let level = Level::parse("# A comment\nname: Test\ndirection: up\n\nmap:\n#..\n.S.\n...\n").unwrap();
+        assert_eq!(level.name, "Test");
+        assert_eq!(level.grid, Grid::new(3, 3));
+        assert_eq!(level.walls, HashSet::from([Cell::new(0, 2)]));
+        assert_eq!(level.spawn, Cell::new(1, 1));
+        assert_eq!(level.direction, Direction::Up);
+    }
+
+    #[test]
+    fn blank_lines_after_the_map_are_not_rows() {
+        let level = Level::parse("map:\n#S.#\n\n\n").unwrap();
+        assert_eq!(level.grid, Grid::new(4, 1));
+
+        // In the middle of the map they are
+        assert!(matches!(parse_error("map:\n.S..\n\n....\n"), (3, Problem::EmptyRow)));
+    }
+
+    #[test]
+    fn header_errors() {
+        assert!(matches!(parse_error("name: Test\nsize: 3 3\n"), (2, Problem::MissingMap)));
+        assert!(matches!(parse_error(""), (1, Problem::MissingMap)));
+        assert!(
+            matches!(parse_error("name: A\nname: B\nmap:\nS.\n"), (2, Problem::DuplicateKey(key)) if key == "name")
+        );
+        assert!(matches!(parse_error("\nwalls\nmap:\nS.\n"), (2, Problem::NotKeyValue)));
+        assert!(matches!(parse_error("size: big\nmap:\nS.\n"), (1, Problem::BadSize(_))));
+    }
+
+    #[test]
+    fn map_errors() {
+        let too_wide = format!("map:\nS{}\n", ".".repeat(MAX_SIZE as usize));
+        assert!(matches!(parse_error(&too_wide), (1, Problem::TooBig)));
+        assert!(matches!(
+            parse_error("name: Test\nmap:\n#S..\n##\n"),
+            (4, Problem::RowWidth { width: 2, expected: 4 })
+        ));
+        assert!(matches!(parse_error("map:\n.S.\n.x.\n"), (3, Problem::UnknownCell('x', 2))));
+        assert!(matches!(
+            parse_error("size: 4 4\nmap:\nS..\n...\n"),
+            (2, Problem::SizeMismatch { size, map }) if size == Grid::new(4, 4) && map == Grid::new(3, 2)
+        ));
+    }
+
+    #[test]
+    fn spawn_errors() {
+        assert!(matches!(parse_error("map:\nS..\n.S.\n"), (3, Problem::SeveralSpawns)));
+        assert!(matches!(parse_error("map:\n...\n...\n"), (1, Problem::NoSpawn)));
+        // Right into the wall, and left out of the board
+        assert!(matches!(parse_error("map:\n...\n.S#\n"), (3, Problem::BlockedSpawn)));
+        assert!(matches!(parse_error("direction: left\nmap:\n...\nS..\n"), (4, Problem::BlockedSpawn)));
+    }
+}
@@ -0,0 +1,876 @@
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use nannou::prelude::*;
+use nannou_egui::egui;
+use support::{Bounds, Input, Options, Sketch};
+
+use crate::ai::{SnakeController, Strategy};
+use crate::difficulty::{Curve, Difficulty};
+use crate::driver::Driver;
+use crate::food::{Food, FoodKind};
+use crate::game::{Game, Outcome};
+use crate::grid::{Boundary, Direction, Grid};
+use crate::highscores::{Entry, HighScores};
+use crate::layout::Layout;
+use crate::level::Level;
+use crate::net::Client;
+use crate::replay::{Player, Recording};
+use crate::session::State;
+use crate::ticker::Ticker;
+
+pub mod ai;
+pub mod cli;
+pub mod difficulty;
+pub mod driver;
+pub mod env;
+pub mod food;
+pub mod game;
+pub mod grid;
+pub mod highscores;
+mod input;
+mod layout;
+pub mod level;
+pub mod net;
+mod render;
+pub mod replay;
+pub mod session;
+mod ticker;
+pub mod tui;
+mod wire;
+
+// Size of the window the game was made for
+pub const SIZE: (u32, u32) = (800, 800);
+
+// How much faster or slower than normal replays can go
+const MIN_REPLAY_SPEED: f32 = 0.25;
+const MAX_REPLAY_SPEED: f32 = 16.;
+
+// One per player, in order. Online games can have more players than colors, then they repeat
+const PLAYER_COLORS: [Srgb<u8>; 4] = [WHITE, DEEPSKYBLUE, GOLD, VIOLET];
+const COLOR_NAMES: [&str; 4] = ["White", "Blue", "Gold", "Violet"];
+// In local two player games player 1 steers with the arrows and player 2 with WASD
+const PLAYER_NAMES: [&str; 2] = ["Arrows", "WASD"];
+
+const MAX_ADDRESS_LENGTH: usize = 64;
+
+// Food that's about to go away blinks for this many ticks
+const FOOD_BLINK_TICKS: u32 = 15;
+
+// Sizes the open level can be set to in the settings window, the other levels have their own
+const MIN_OPEN_SIZE: i32 = 8;
+const MAX_OPEN_SIZE: i32 = 80;
+
+// A recording being watched, it takes over the screen until it's closed
+struct Replay {
+    player: Player,
+    paused: bool,
+    speed: f32, // Times the normal tick rate
+    ticker: Ticker,
+}
+
+// A game on a server, it takes over the screen until it's left
+struct Online {
+    client: Client,
+    ticker: Ticker, // Runs at the server's tick rate
+    controller: Box<dyn SnakeController>, // For the autopilot
+}
+
+// The nannou side of the game, the game itself is in `game`, `session` and `driver`
+pub struct Model {
+    driver: Driver,
+    smooth_snakes: bool, // Rounded snakes with eyes instead of squares
+    // Snakes in local games and replays. Online games always use `PLAYER_COLORS`, players are told their color by name
+    colors: [Srgb<u8>; 2],
+    board_color: Srgb<u8>,
+    show_hud: bool,
+    fps: f32, // Averaged over the last few frames
+    bounds: Bounds, // From the last step, used to fit the grid on the screen
+    high_scores: HighScores,
+    high_scores_path: Option<PathBuf>, // None if the table shouldn't be saved
+    name_entry: Option<String>, // Name being typed on the game over screen after a high score
+    score_checked: bool, // If the score of the game that just ended was already checked against the table
+    last_rank: Option<usize>, // Where the last name went on the table, to highlight it
+    replay: Option<Replay>,
+    online: Option<Online>,
+    address_entry: Option<String>, // Server address being typed on the title screen
+    address: String,               // The last one, so it doesn't have to be typed again
+    message: Option<String>,       // Shown on the game over and title screens, like where a replay was saved
+}
+
+// Draws a line of text centered at `y` as wide as the window, so it doesn't wrap. `draw` is from `Model::text_draw`,
+// where the window is always `SIZE`
+fn text_line(draw: &Draw, text: &str, y: f32, size: u32) {
+    draw.text(text).x_y(0., y).w(SIZE.0 as f32).font_size(size).color(WHITE);
+}
+
+// Position on one axis between the last two ticks. When the snake went through the edge the two cells are on
+// opposite sides of the board, then it goes the short way out of the edge instead of back across the whole board
+fn lerp_axis(from: i32, to: i32, size: i32, alpha: f32) -> f32 {
+    let mut delta = to - from;
+    if delta.abs() > 1 {
+        delta -= delta.signum() * size;
+    }
+    from as f32 + delta as f32 * alpha
+}
+
+// A segment halfway out of one edge also has to show up halfway into the opposite one
+fn wrapped_copies(position: f32, size: i32) -> Vec<f32> {
+    let size = size as f32;
+    if position < 0. {
+        vec![position, position + size]
+    } else if position > size - 1. {
+        vec![position, position - size]
+    } else {
+        vec![position]
+    }
+}
+
+// Color picker for one of the colors in the settings window
+fn color_button(ui: &mut egui::Ui, color: &mut Srgb<u8>) {
+    let mut rgb = [color.red, color.green, color.blue];
+    if ui.color_edit_button_srgb(&mut rgb).changed() {
+        *color = Srgb::new(rgb[0], rgb[1], rgb[2]);
+    }
+}
+
+// Food that's about to go away is hidden every other tick
+fn food_blinked_out(food: &Food) -> bool {
+    food.ticks_left.is_some_and(|ticks| ticks < FOOD_BLINK_TICKS && ticks % 2 == 0)
+}
+
+// Every kind of food has its own color and shape
+fn draw_food(draw: &Draw, food: &Food, position: Point2, size: f32) {
+    if food_blinked_out(food) {
+        return;
+    }
+    let half = size / 2.;
+    match food.kind {
+        FoodKind::Normal => {
+            draw.quad().xy(position).w_h(size, size).color(RED);
+        }
+        FoodKind::Big => {
+            draw.ellipse().xy(position).w_h(size * 1.2, size * 1.2).color(ORANGE);
+        }
+        FoodKind::Fast => {
+            let (left, right, top) = (pt2(-half, -half), pt2(half, -half), pt2(0., half));
+            draw.tri().points(position + left, position + right, position + top).color(YELLOW);
+        }
+        FoodKind::Slow => {
+            let (left, right, bottom) = (pt2(-half, half), pt2(half, half), pt2(0., -half));
+            draw.tri().points(position + left, position + right, position + bottom).color(LIGHTSEAGREEN);
+        }
+        FoodKind::Shrink => {
+            draw.quad().xy(position).w_h(half, half).color(HOTPINK);
+        }
+        FoodKind::Double => {
+            draw.quad().xy(position).w_h(size * 0.75, size * 0.75).rotate(PI / 4.).color(LIME);
+        }
+    }
+}
+
+// Power-ups that are running, empty if there are none
+fn effects_line(game: &Game, names: &[&str]) -> String {
+    let mut effects = Vec::new();
+    if let Some((kind, ticks)) = game.speed_change() {
+        effects.push(format!("{}: {}", kind.name(), ticks));
+    }
+    for (i, snake) in game.snakes().iter().enumerate().filter(|(_, snake)| snake.double_ticks() > 0) {
+        if game.snakes().len() > 1 {
+            effects.push(format!("Double points for {}: {}", names[i % names.len()], snake.double_ticks()));
+        } else {
+            effects.push(format!("Double points: {}", snake.double_ticks()));
+        }
+    }
+    effects.join("   ")
+}
+
+fn color_name(player: usize) -> &'static str {
+    COLOR_NAMES[player % COLOR_NAMES.len()]
+}
+
+// Every player's score, for the HUD and the end of games with more than one player
+fn scores_line(game: &Game, names: &[&str]) -> String {
+    let scores: Vec<String> = game
+        .snakes()
+        .iter()
+        .enumerate()
+        .map(|(i, snake)| format!("{}: {}", names[i % names.len()], snake.score()))
+        .collect();
+    scores.join("   ")
+}
+
+// Big text on the game over screen
+fn game_over_title(game: &Game) -> String {
+    match game.outcome() {
+        Some(Outcome::Won) => "YOU WIN!".to_string(),
+        Some(Outcome::Winner(player)) => format!("{} WINS!", PLAYER_NAMES[player].to_uppercase()),
+        Some(Outcome::Draw) => "DRAW!".to_string(),
+        _ => "GAME OVER!".to_string(),
+    }
+}
+
+// Reads the high score table, if it can't be read it also won't be saved so a good (but unreadable) file doesn't
+// get overwritten
+fn load_high_scores() -> (HighScores, Option<PathBuf>) {
+    let path = match HighScores::default_path() {
+        Some(path) => path,
+        None => return (HighScores::default(), None),
+    };
+    match HighScores::load(&path) {
+        Ok(high_scores) => (high_scores, Some(path)),
+        Err(err) => {
+            eprintln!("{}", err);
+            (HighScores::default(), None)
+        }
+    }
+}
+
+// The bundled levels and then the player's own, broken ones are left out
+fn load_levels() -> Vec<Level> {
+    let mut levels = Level::bundled();
+    if let Some(dir) = Level::user_dir() {
+        for (path, level) in Level::load_dir(&dir) {
+            match level {
+                Ok(level) => levels.push(level),
+                Err(err) => eprintln!("{}: {}", path.display(), err),
+            }
+        }
+    }
+    levels
+}
+
+impl Model {
+    // Text is laid out for a window of `SIZE` and scaled with the window, so it fits at any size like the board does
+    fn text_draw(&self, draw: &Draw) -> Draw {
+        draw.scale(self.text_scale())
+    }
+
+    fn text_scale(&self) -> f32 {
+        let scale = (self.bounds.width as f32 / SIZE.0 as f32).min(self.bounds.height as f32 / SIZE.1 as f32);
+        // A minimized window can be 0 wide
+        scale.max(0.01)
+    }
+
+    // Top edge of the window for text drawn with `text_draw`
+    fn text_top(&self) -> f32 {
+        self.bounds.half_height() / self.text_scale()
+    }
+
+    fn restart(&mut self) {
+        self.driver.restart();
+        self.reset_game_flags();
+    }
+
+    fn back_to_title(&mut self) {
+        self.driver.back_to_title();
+        self.reset_game_flags();
+    }
+
+    fn reset_game_flags(&mut self) {
+        self.message = None;
+        self.score_checked = false;
+        self.name_entry = None;
+    }
+
+    fn submit_name(&mut self) {
+        let name = match self.name_entry.take() {
+            Some(name) => name,
+            None => return,
+        };
+        let game = self.driver.session().game();
+        self.last_rank = self.high_scores.insert(Entry { name, score: game.score(), length: game.snake().length() });
+        if let Some(path) = &self.high_scores_path {
+            if let Err(err) = self.high_scores.save(path) {
+                eprintln!("{}", err);
+            }
+        }
+        self.driver.session_mut().show_high_scores();
+    }
+
+    fn draw_high_scores(&self, draw: &Draw) {
+        text_line(draw, "HIGH SCORES", 300., 32);
+        if self.high_scores.entries().is_empty() {
+            text_line(draw, "Nobody has played yet", 200., 20);
+        }
+        for (i, entry) in self.high_scores.entries().iter().enumerate() {
+            let marker = if self.last_rank == Some(i) { ">" } else { " " };
+            let line =
+                format!("{} {:>2}. {:<12} {:>6}  length {}", marker, i + 1, entry.name, entry.score, entry.length);
+            text_line(draw, &line, 220. - i as f32 * 36., 20);
+        }
+        text_line(draw, "Escape to go back", -200., 16);
+    }
+
+    fn watch(&mut self, recording: Recording) {
+        let ticker = Ticker::new(self.driver.speed_curve().start);
+        self.replay = Some(Replay { player: Player::new(recording), paused: false, speed: 1., ticker });
+    }
+
+    // Saves the game that just ended next to the other replays, named after the time so they sort by age
+    fn save_replay(&mut self) {
+        let dir = match Recording::default_dir() {
+            Some(dir) => dir,
+            None => return,
+        };
+        let time = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_secs());
+        let path = dir.join(format!("replay-{}.snkr", time));
+        self.message = match Recording::of(self.driver.session().game()).save(&path) {
+            Ok(()) => Some(format!("Saved to {}", path.display())),
+            Err(err) => {
+                eprintln!("{}", err);
+                Some("Couldn't save the replay".to_string())
+            }
+        };
+    }
+
+    // The newest saved replay
+    fn open_replay(&mut self) {
+        let path = match Recording::default_dir().and_then(|dir| Recording::newest_in(&dir)) {
+            Some(path) => path,
+            None => {
+                self.message = Some("There are no saved replays yet".to_string());
+                return;
+            }
+        };
+        match Recording::load(&path) {
+            Ok(recording) => self.watch(recording),
+            Err(err) => {
+                eprintln!("{}: {}", path.display(), err);
+                self.message = Some("Couldn't open the replay".to_string());
+            }
+        }
+    }
+
+    // Connecting can take a few seconds when nobody answers, the window waits for it
+    fn join(&mut self) {
+        let address = match self.address_entry.take() {
+            Some(address) => address.trim().to_string(),
+            None => return,
+        };
+        let full_address =
+            if address.contains(':') { address.clone() } else { format!("{}:{}", address, net::DEFAULT_PORT) };
+        match Client::connect(full_address.as_str()) {
+            Ok(client) => {
+                let ticker = Ticker::new(client.ticks_per_second());
+                self.online = Some(Online { ticker, controller: self.driver.strategy().controller(), client });
+                self.message = None;
+            }
+            Err(err) => self.message = Some(format!("Couldn't join {}: {}", full_address, err)),
+        }
+        self.address = address;
+    }
+
+    fn leave_online(&mut self, message: String) {
+        self.online = None;
+        self.message = Some(message);
+    }
+
+    fn step_online(&mut self, dt: f32) {
+        let online = match &mut self.online {
+            Some(online) => online,
+            None => return,
+        };
+        let Online { client, ticker, controller } = online;
+        let speed = client.game().map_or(1., Game::speed);
+        let result = client.update(ticker.advance(dt * speed)).and_then(|played| match client.game() {
+            Some(game) if played > 0 && self.driver.autopilot() && !game.is_over() => {
+                let direction = controller.decide(game, client.player());
+                client.turn(direction)
+            }
+            _ => Ok(()),
+        });
+        if let Err(err) = result {
+            self.leave_online(format!("Left the online game, {}", err));
+        }
+    }
+
+    fn draw_online(&self, draw: &Draw, online: &Online) {
+        let text = self.text_draw(draw);
+        let client = &online.client;
+        let me = client.player();
+        let game = match client.game() {
+            Some(game) => game,
+            None => {
+                text_line(&text, "ONLINE", 40., 32);
+                text_line(&text, "Waiting for the other players", -10., 20);
+                text_line(&text, &format!("You're {}, Escape to leave", color_name(me).to_lowercase()), -50., 16);
+                return;
+            }
+        };
+        self.draw_board(draw, game, &PLAYER_COLORS, online.ticker.alpha());
+        let top = self.text_top();
+        text_line(&text, &scores_line(game, &COLOR_NAMES), top - 20., 16);
+        text_line(&text, &format!("You're {}, Escape to leave", color_name(me).to_lowercase()), top - 44., 14);
+        text_line(&text, &effects_line(game, &COLOR_NAMES), -top + 20., 14);
+        if game.is_over() {
+            let title = match game.outcome() {
+                Some(Outcome::Won) => "YOU WIN!".to_string(),
+                Some(Outcome::Winner(player)) if player == me => "YOU WIN!".to_string(),
+                Some(Outcome::Winner(player)) => format!("{} WINS!", color_name(player).to_uppercase()),
+                Some(Outcome::Draw) => "DRAW!".to_string(),
+                _ => "GAME OVER!".to_string(),
+            };
+            text_line(&text, &title, 40., 32);
+            text_line(&text, "The next round starts soon", -10., 16);
+        }
+    }
+
+    fn handle_online_key(&mut self, key: Key) {
+        let direction = match key {
+            Key::Escape => return self.leave_online("Left the online game".to_string()),
+            Key::A => return self.driver.toggle_autopilot(),
+            _ if self.driver.autopilot() => return,
+            Key::Up => Direction::Up,
+            Key::Down => Direction::Down,
+            Key::Left => Direction::Left,
+            Key::Right => Direction::Right,
+            _ => return,
+        };
+        let result = match &mut self.online {
+            Some(online) => online.client.turn(direction),
+            None => return,
+        };
+        if let Err(err) = result {
+            self.leave_online(format!("Left the online game, {}", err));
+        }
+    }
+
+    fn draw_replay(&self, draw: &Draw, replay: &Replay) {
+        let text = self.text_draw(draw);
+        let game = replay.player.game();
+        self.draw_board(draw, game, &self.colors, replay.ticker.alpha());
+        let recording = replay.player.recording();
+        let status = if replay.player.is_finished() {
+            let scores: Vec<String> = game.snakes().iter().map(|snake| snake.score().to_string()).collect();
+            let recorded: Vec<String> = recording.scores.iter().map(|score| score.to_string()).collect();
+            format!("Finished, score {} (recorded {})", scores.join("/"), recorded.join("/"))
+        } else if replay.paused {
+            "Paused".to_string()
+        } else {
+            format!("x{}", replay.speed)
+        };
+        let top = self.text_top();
+        text_line(&text, &format!("REPLAY   tick {}/{}   {}", game.ticks(), recording.ticks, status), top - 20., 16);
+        let help = "Space to pause, Right to step, Up/Down for speed, R to rewind, Escape to close";
+        text_line(&text, help, top - 44., 14);
+    }
+
+    fn handle_replay_key(&mut self, key: Key) {
+        let replay = match &mut self.replay {
+            Some(replay) => replay,
+            None => return,
+        };
+        match key {
+            Key::Space | Key::P => replay.paused = !replay.paused,
+            Key::Right if replay.paused => replay.player.tick(),
+            Key::Up => replay.speed = (replay.speed * 2.).min(MAX_REPLAY_SPEED),
+            Key::Down => replay.speed = (replay.speed / 2.).max(MIN_REPLAY_SPEED),
+            Key::R => replay.player.restart(),
+            Key::Escape => self.replay = None,
+            _ => {}
+        }
+    }
+
+    // `alpha` is how far it is between the last tick and the next one
+    fn draw_board(&self, draw: &Draw, game: &Game, colors: &[Srgb<u8>], alpha: f32) {
+        let layout = Layout::new(game.grid(), self.bounds);
+        let block_size = layout.cell_size();
+
+        // Board, so it's visible where the walls are when the window isn't the same shape as the grid
+        let board = layout.board_size();
+        draw.rect().w_h(board.x, board.y).color(self.board_color);
+
+        // Walls
+        for wall in &game.level().walls {
+            draw.quad().xy(layout.to_screen(wall.x as f32, wall.y as f32)).w_h(block_size, block_size).color(GRAY);
+        }
+
+        // Food
+        for food in game.food() {
+            draw_food(draw, food, layout.to_screen(food.cell.x as f32, food.cell.y as f32), block_size);
+        }
+
+        // Snakes, drawn between the last two ticks so they move smoothly at any frame rate. Cut off at the board so the
+        // parts going through the edge in wrap mode don't stick out. With more than one player the dead ones are
+        // taken off the board
+        let draw = draw.scissor(Rect::from_w_h(board.x, board.y));
+        let grid = game.grid();
+        for (i, snake) in game.snakes().iter().enumerate() {
+            let color = colors[i % colors.len()];
+            if !snake.is_alive() && game.snakes().len() > 1 {
+                continue;
+            }
+            if self.smooth_snakes {
+                render::draw_snake(&draw, &layout, game, snake, color, alpha);
+                continue;
+            }
+            for (i, segment) in snake.body().enumerate() {
+                let prev = snake.previous(i);
+                let x = lerp_axis(prev.x, segment.x, grid.width, alpha);
+                let y = lerp_axis(prev.y, segment.y, grid.height, alpha);
+                for x in wrapped_copies(x, grid.width) {
+                    for y in wrapped_copies(y, grid.height) {
+                        draw.quad().xy(layout.to_screen(x, y)).w_h(block_size, block_size).color(color);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Sketch for Model {
+    fn init(bounds: Bounds, options: &Options) -> Model {
+        let (high_scores, high_scores_path) = load_high_scores();
+        Model {
+            driver: Driver::new(load_levels(), options.seed),
+            smooth_snakes: true,
+            colors: [PLAYER_COLORS[0], PLAYER_COLORS[1]],
+            board_color: Srgb::new(15, 15, 15),
+            show_hud: true,
+            fps: 0.,
+            bounds,
+            high_scores,
+            high_scores_path,
+            name_entry: None,
+            score_checked: false,
+            last_rank: None,
+            replay: None,
+            online: None,
+            address_entry: None,
+            address: format!("127.0.0.1:{}", net::DEFAULT_PORT),
+            message: None,
+        }
+    }
+
+    fn step(&mut self, bounds: Bounds, dt: f32) {
+        self.bounds = bounds;
+        if dt > 0. {
+            self.fps = if self.fps > 0. { self.fps * 0.95 + 0.05 / dt } else { 1. / dt };
+        }
+        if let Some(replay) = &mut self.replay {
+            if !replay.paused {
+                replay.ticker.ticks_per_second = self.driver.speed_curve().ticks_per_second(replay.player.game());
+                let speed = replay.speed * replay.player.game().speed();
+                for _ in 0..replay.ticker.advance(dt * speed) {
+                    replay.player.tick();
+                }
+            }
+            return;
+        }
+        if self.online.is_some() {
+            self.step_online(dt);
+            return;
+        }
+        self.driver.step(dt);
+
+        // Just died, ask for a name if it made it onto the table. Two player games don't count
+        if self.driver.session().state() == State::GameOver && !self.score_checked {
+            self.score_checked = true;
+            let game = self.driver.session().game();
+            if !self.driver.bot_played() && game.snakes().len() == 1 && self.high_scores.qualifies(game.score()) {
+                self.name_entry = Some(String::new());
+            }
+        }
+    }
+
+    fn draw(&self, draw: &Draw) {
+        if let Some(replay) = &self.replay {
+            self.draw_replay(draw, replay);
+            return;
+        }
+        if let Some(online) = &self.online {
+            self.draw_online(draw, online);
+            return;
+        }
+        let text = self.text_draw(draw);
+        if let Some(address) = &self.address_entry {
+            text_line(&text, "Join a game at:", 30., 20);
+            text_line(&text, &format!("{}_", address), 0., 20);
+            text_line(&text, "Enter to connect, Escape to go back", -40., 16);
+            return;
+        }
+        let driver = &self.driver;
+        if let (Some(message), State::Title | State::GameOver) = (&self.message, driver.session().state()) {
+            text_line(&text, message, -self.text_top() + 30., 14);
+        }
+        match driver.session().state() {
+            State::Title => {
+                text_line(&text, "SNAKE", 40., 48);
+                text_line(&text, "Press Enter to start", -20., 20);
+                text_line(&text, &format!("< {} >", driver.level().name), -70., 20);
+                let edges = match driver.session().config().boundary {
+                    Boundary::Solid => "solid",
+                    Boundary::Wrap => "wrap around",
+                };
+                let difficulty = Difficulty::of(driver.speed_curve()).map_or("Custom", Difficulty::name);
+                text_line(&text, &format!("Edges: {}   Difficulty: {}", edges, difficulty), -100., 16);
+                let keys = "Left and right to pick a level, W for the edges, D for the difficulty, H for high scores";
+                text_line(&text, keys, -130., 16);
+                text_line(&text, "O to watch the last saved replay, J to join an online game", -160., 16);
+                let players = match driver.session().config().players {
+                    1 => "1 player (2 for two players)".to_string(),
+                    players => format!("{} players, arrows against WASD (1 for one player)", players),
+                };
+                text_line(&text, &players, -190., 16);
+                if driver.autopilot() {
+                    text_line(&text, &format!("Autopilot: {} (A to turn off)", driver.strategy().name()), -220., 16);
+                }
+            }
+            State::HighScores => self.draw_high_scores(&text),
+            State::Playing => {
+                let game = driver.session().game();
+                self.draw_board(draw, game, &self.colors, driver.alpha());
+                let top = self.text_top();
+                let mut lines = Vec::new();
+                if self.show_hud {
+                    let scores = if game.snakes().len() > 1 {
+                        scores_line(game, &PLAYER_NAMES)
+                    } else {
+                        format!("Score: {}   Length: {}", game.score(), game.snake().length())
+                    };
+                    let seconds = driver.play_time() as u32;
+                    lines.push(format!("{}   Time: {}:{:02}", scores, seconds / 60, seconds % 60));
+                    let level = driver.speed_curve().level(game) + 1;
+                    let speed = format!("Speed level {} ({:.1} ticks/s)", level, driver.ticks_per_second());
+                    lines.push(format!("{}   {:.0} FPS", speed, self.fps));
+                }
+                if driver.autopilot() {
+                    lines.push(format!("Autopilot: {}", driver.strategy().name()));
+                }
+                for (i, line) in lines.iter().enumerate() {
+                    text_line(&text, line, top - 20. - 24. * i as f32, 16);
+                }
+                text_line(&text, &effects_line(game, &PLAYER_NAMES), -top + 20., 14);
+            }
+            State::Paused => {
+                self.draw_board(draw, driver.session().game(), &self.colors, driver.alpha());
+                text_line(&text, "PAUSED", 0., 32);
+                text_line(&text, "P to resume, R to restart", -40., 16);
+            }
+            State::GameOver => {
+                let game = driver.session().game();
+                text_line(&text, &game_over_title(game), 40., 32);
+                if game.snakes().len() > 1 {
+                    text_line(&text, &scores_line(game, &PLAYER_NAMES), -10., 20);
+                } else {
+                    text_line(&text, &format!("Score: {}   Length: {}", game.score(), game.snake().length()), -10., 20);
+                }
+                match &self.name_entry {
+                    Some(name) => {
+                        text_line(&text, "New high score! Type your name:", -60., 16);
+                        text_line(&text, &format!("{}_", name), -90., 20);
+                        text_line(&text, "Enter to save", -120., 16);
+                    }
+                    None => {
+                        text_line(&text, "R to play again, Escape for the title screen", -50., 16);
+                        text_line(&text, "V to watch the replay, S to save it", -80., 16);
+                    }
+                }
+            }
+        }
+    }
+
+    // Keys come in at the frame rate, not the tick rate, so turns only get queued here and used up in `Game::tick`
+    fn handle_input(&mut self, input: &Input) {
+        let key = match input {
+            Input::KeyPressed(key) => *key,
+            Input::Char(c) => {
+                if let Some(name) = &mut self.name_entry {
+                    if !c.is_control() && highscores::name_has_room(name) {
+                        name.push(*c);
+                    }
+                }
+                if let Some(address) = &mut self.address_entry {
+                    if !c.is_control() && address.len() < MAX_ADDRESS_LENGTH {
+                        address.push(*c);
+                    }
+                }
+                return;
+            }
+            _ => return,
+        };
+
+        if self.replay.is_some() {
+            self.handle_replay_key(key);
+            return;
+        }
+        if self.online.is_some() {
+            self.handle_online_key(key);
+            return;
+        }
+        if let Some(address) = &mut self.address_entry {
+            match key {
+                Key::Back => {
+                    address.pop();
+                }
+                Key::Return => self.join(),
+                Key::Escape => self.address_entry = None,
+                _ => {}
+            }
+            return;
+        }
+
+        // While typing a name the letters shouldn't also restart or pause the game
+        if let Some(name) = &mut self.name_entry {
+            match key {
+                Key::Back => {
+                    name.pop();
+                }
+                Key::Return => self.submit_name(),
+                _ => {}
+            }
+            return;
+        }
+
+        // In two player games WASD belongs to the second player, so A doesn't toggle the autopilot mid game
+        let two_players = self.driver.session().config().players > 1;
+        match (self.driver.session().state(), key) {
+            (State::Playing, Key::W) if two_players => self.driver.steer(1, Direction::Up),
+            (State::Playing, Key::S) if two_players => self.driver.steer(1, Direction::Down),
+            (State::Playing, Key::A) if two_players => self.driver.steer(1, Direction::Left),
+            (State::Playing, Key::D) if two_players => self.driver.steer(1, Direction::Right),
+            (State::Title | State::Playing | State::Paused, Key::A) => self.driver.toggle_autopilot(),
+            (State::Title, Key::Return | Key::Space) => self.driver.session_mut().start(),
+            (State::Title, Key::H) => self.driver.session_mut().show_high_scores(),
+            (State::Title, Key::O) => self.open_replay(),
+            (State::Title, Key::J) => self.address_entry = Some(self.address.clone()),
+            (State::GameOver, Key::V) => self.watch(Recording::of(self.driver.session().game())),
+            (State::GameOver, Key::S) => self.save_replay(),
+            (State::Title, Key::Left) => self.driver.change_level(-1),
+            (State::Title, Key::Right) => self.driver.change_level(1),
+            (State::Title, Key::Key1) => self.driver.session_mut().set_players(1),
+            (State::Title, Key::Key2) => self.driver.session_mut().set_players(2),
+            (State::Title, Key::D) => self.driver.next_difficulty(),
+            (State::Title, Key::W) => self.driver.toggle_boundary(),
+            (State::HighScores, Key::Escape | Key::H | Key::Return) => self.back_to_title(),
+            (State::Playing | State::Paused, Key::P | Key::Escape) => self.driver.session_mut().toggle_pause(),
+            (State::Playing | State::Paused | State::GameOver, Key::R) => self.restart(),
+            (State::GameOver, Key::Return) => self.restart(),
+            (State::GameOver, Key::Escape) => self.back_to_title(),
+            // Does nothing while the bot is steering
+            (_, Key::Up) => self.driver.steer(0, Direction::Up),
+            (_, Key::Down) => self.driver.steer(0, Direction::Down),
+            (_, Key::Left) => self.driver.steer(0, Direction::Left),
+            (_, Key::Right) => self.driver.steer(0, Direction::Right),
+            _ => {}
+        }
+    }
+
+    fn settings_ui(&mut self, ui: &mut egui::Ui) {
+        ui.label("These apply right away:");
+        let mut autopilot = self.driver.autopilot();
+        if ui.checkbox(&mut autopilot, "Autopilot (A)").changed() {
+            self.driver.set_autopilot(autopilot);
+        }
+        let mut strategy = self.driver.strategy();
+        egui::ComboBox::from_label("Bot").selected_text(strategy.name()).show_ui(ui, |ui| {
+            for variant in Strategy::ALL {
+                ui.selectable_value(&mut strategy, variant, variant.name());
+            }
+        });
+        if strategy != self.driver.strategy() {
+            self.driver.set_strategy(strategy);
+        }
+
+        ui.separator();
+        let difficulty = Difficulty::of(self.driver.speed_curve()).map_or("Custom", Difficulty::name);
+        egui::ComboBox::from_label("Difficulty").selected_text(difficulty).show_ui(ui, |ui| {
+            for variant in Difficulty::ALL {
+                if ui.selectable_label(difficulty == variant.name(), variant.name()).clicked() {
+                    *self.driver.speed_curve_mut() = variant.curve();
+                }
+            }
+        });
+        let curve = self.driver.speed_curve_mut();
+        egui::ComboBox::from_label("Speed curve").selected_text(curve.curve.name()).show_ui(ui, |ui| {
+            for variant in Curve::ALL {
+                ui.selectable_value(&mut curve.curve, variant, variant.name());
+            }
+        });
+        ui.label("Ticks per second at the start, and at most:");
+        ui.add(egui::Slider::new(&mut curve.start, 1. ..= 60.));
+        ui.add(egui::Slider::new(&mut curve.max, 1. ..= 60.));
+        let increase = match curve.curve {
+            Curve::Exponential => "How much faster every level is (0.1 is 10%):",
+            _ => "Ticks per second added every level:",
+        };
+        ui.label(increase);
+        ui.add(egui::Slider::new(&mut curve.increase, 0. ..= 5.));
+        ui.label("Segments per level:");
+        ui.add(egui::Slider::new(&mut curve.segments_per_level, 1..=20));
+
+        ui.separator();
+        ui.checkbox(&mut self.show_hud, "Show the score, time and speed while playing");
+        ui.checkbox(&mut self.smooth_snakes, "Smooth snakes");
+        ui.horizontal(|ui| {
+            color_button(ui, &mut self.colors[0]);
+            ui.label("Snake (arrows)");
+            color_button(ui, &mut self.colors[1]);
+            ui.label("Snake (WASD)");
+            color_button(ui, &mut self.board_color);
+            ui.label("Board");
+        });
+
+        ui.separator();
+        // Part of the session's config, a game keeps the config it started with
+        ui.label("These are used from the next game on, R restarts:");
+        let config = self.driver.session().config();
+        let open = self.driver.levels()[0].grid;
+        let (mut width, mut height) = (open.width, open.height);
+        ui.label("Size of the open level:");
+        let resized = ui.add(egui::Slider::new(&mut width, MIN_OPEN_SIZE..=MAX_OPEN_SIZE).text("wide")).changed()
+            | ui.add(egui::Slider::new(&mut height, MIN_OPEN_SIZE..=MAX_OPEN_SIZE).text("high")).changed();
+        let mut wrap = config.boundary == Boundary::Wrap;
+        let wrap_changed = ui.checkbox(&mut wrap, "Wrap around the edges (W)").changed();
+        let mut food_count = config.food_count;
+        ui.label("Food on the board:");
+        let food_count_changed = ui.add(egui::Slider::new(&mut food_count, 1..=20)).changed();
+        ui.label("How often every kind of food spawns:");
+        let mut weights = config.food_weights;
+        let mut weights_changed = false;
+        for kind in FoodKind::ALL {
+            let slider = egui::Slider::new(&mut weights[kind.index()], 0..=20).text(kind.name());
+            weights_changed |= ui.add(slider).changed();
+        }
+        if resized {
+            self.driver.replace_level(0, Level::open(Grid::new(width, height)));
+        }
+        if wrap_changed {
+            self.driver.session_mut().set_boundary(if wrap { Boundary::Wrap } else { Boundary::Solid });
+        }
+        if food_count_changed {
+            self.driver.session_mut().set_food_count(food_count);
+        }
+        if weights_changed {
+            self.driver.session_mut().set_food_weights(weights);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Cell;
+
+    // Same as `nannou-projects simulate`, no window needed. The bot plays so something happens
+    #[test]
+    fn runs_headless() {
+        let bounds = Bounds::new(SIZE.0, SIZE.1);
+        let inputs = [Input::KeyPressed(Key::A), Input::KeyPressed(Key::Return)];
+        let model: Model = support::run_headless(bounds, &Options { amount: None, seed: 1 }, &inputs, 600);
+        assert!(model.driver.session().game().ticks() > 0);
+        assert!(model.driver.bot_played());
+    }
+
+    // Where the snake and the food are after the bot played for a few seconds
+    #[test]
+    fn same_seed_same_run() {
+        let inputs = [Input::KeyPressed(Key::A), Input::KeyPressed(Key::Return)];
+        let options = Options { amount: None, seed: 7 };
+        support::assert_same_seed_same_run(Bounds::new(SIZE.0, SIZE.1), &options, &inputs, |model: &Model| {
+            let game = model.driver.session().game();
+            (game.ticks(), game.snake().body().collect::<Vec<Cell>>(), game.food().to_vec())
+        });
+    }
+}
@@ -0,0 +1,716 @@
+// Snake over the network. The server plays the real game and decides on which tick every turn happens, clients send
+// it their turns and play the same game from what it sends back.
+//
+// Games are deterministic (see `replay`), so instead of the whole board every tick the server only sends the seed when
+// a round starts and then one frame per tick with the turns that happen on it. Frames go out `input_delay` ticks
+// before the server plays them, so as long as the lag is shorter than that every client already has the next frame
+// when it needs it and the snakes move smoothly. The price is that a turn happens that many ticks after the key was
+// pressed, for everyone the same.
+//
+// Every client has one TCP connection. It starts with "SNKN" and the protocol version, after that both sides send
+// messages that are their length followed by a kind and the rest, numbers are varints like in `wire`:
+//
+//   client 0 turn:     direction
+//   server 0 welcome:  player tick-length(microseconds) input-delay config
+//          1 rejected: reason
+//          2 start:    seed
+//          3 frame:    tick turn-count (player direction)...
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{self, BufReader, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver, Sender, SyncSender, TryRecvError, TrySendError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+use support::SimRng;
+
+use crate::game::{Config, Game, MAX_PLAYERS};
+use crate::grid::Direction;
+use crate::replay::Recording;
+use crate::wire::{self, direction_code, write_config, write_string, write_varint, Reader};
+
+// Bump this when the messages change. It also has to go up when the game itself changes in a way that plays out
+// differently, otherwise clients and the server end up with different games
+pub const VERSION: u64 = 2;
+pub const DEFAULT_PORT: u16 = 7777;
+
+const MAGIC: &[u8; 4] = b"SNKN";
+// Messages are tiny, anything longer than this is someone sending garbage
+const MAX_MESSAGE_LENGTH: u64 = 1 << 20;
+// How long connecting and saying hello can take before giving up
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+// A connection where the other side doesn't take what it's sent for this long gets dropped
+const WRITE_TIMEOUT: Duration = Duration::from_secs(1);
+// Messages waiting to be sent on one connection before the other side counts as too slow to play and gets dropped.
+// Ten seconds of frames at the default tick rate
+const MAX_UNSENT: usize = 100;
+// Turns one player can make in one frame, the game only queues a few anyway
+const MAX_TURNS_PER_FRAME: usize = 3;
+// How often the server checks for new players while nobody is playing
+const LOBBY_POLL: Duration = Duration::from_millis(20);
+
+const KIND_TURN: u64 = 0;
+const KIND_WELCOME: u64 = 0;
+const KIND_REJECTED: u64 = 1;
+const KIND_START: u64 = 2;
+const KIND_FRAME: u64 = 3;
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Broken(wire::Error), // The other side sent something that doesn't make sense
+    Rejected(String),    // The server didn't let us in, with why
+    Closed,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "network error: {}", err),
+            Error::Broken(err) => write!(f, "got a broken message: {}", err),
+            Error::Rejected(reason) => write!(f, "the server said no: {}", reason),
+            Error::Closed => write!(f, "the connection was closed"),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Error {
+        match err.kind() {
+            io::ErrorKind::UnexpectedEof | io::ErrorKind::ConnectionReset => Error::Closed,
+            _ => Error::Io(err),
+        }
+    }
+}
+
+impl From<wire::Error> for Error {
+    fn from(err: wire::Error) -> Error {
+        Error::Broken(err)
+    }
+}
+
+// What happened on the server, for whoever runs it to show. See `Server::on_event`
+#[derive(Debug)]
+pub enum ServerEvent {
+    Joined { player: usize, address: SocketAddr },
+    Left { player: usize, address: SocketAddr },
+    Refused { address: SocketAddr, error: Error }, // Turned away, or never said hello properly
+}
+
+impl fmt::Display for ServerEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ServerEvent::Joined { player, address } => write!(f, "player {} joined from {}", player + 1, address),
+            ServerEvent::Left { player, address } => write!(f, "player {} ({}) left", player + 1, address),
+            ServerEvent::Refused { address, error } => write!(f, "{}: {}", address, error),
+        }
+    }
+}
+
+type Turns = Vec<(usize, Direction)>;
+
+// Everything the server sends
+enum Message {
+    Welcome { player: usize, tick_length: Duration, input_delay: u32, config: Config },
+    Rejected(String),
+    Start(u64),
+    Frame { tick: u32, turns: Turns },
+}
+
+impl Message {
+    fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        match self {
+            Message::Welcome { player, tick_length, input_delay, config } => {
+                write_varint(&mut bytes, KIND_WELCOME);
+                write_varint(&mut bytes, *player as u64);
+                write_varint(&mut bytes, tick_length.as_micros() as u64);
+                write_varint(&mut bytes, *input_delay as u64);
+                write_config(&mut bytes, config);
+            }
+            Message::Rejected(reason) => {
+                write_varint(&mut bytes, KIND_REJECTED);
+                write_string(&mut bytes, reason);
+            }
+            Message::Start(seed) => {
+                write_varint(&mut bytes, KIND_START);
+                write_varint(&mut bytes, *seed);
+            }
+            Message::Frame { tick, turns } => {
+                write_varint(&mut bytes, KIND_FRAME);
+                write_varint(&mut bytes, *tick as u64);
+                write_varint(&mut bytes, turns.len() as u64);
+                for (player, direction) in turns {
+                    write_varint(&mut bytes, *player as u64);
+                    write_varint(&mut bytes, direction_code(*direction));
+                }
+            }
+        }
+        bytes
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Message, wire::Error> {
+        let mut reader = Reader { bytes };
+        let message = match reader.varint()? {
+            KIND_WELCOME => Message::Welcome {
+                player: reader.varint()? as usize,
+                tick_length: Duration::from_micros(reader.varint()?),
+                input_delay: u32::try_from(reader.varint()?).map_err(|_| wire::Error::Invalid("input delay"))?,
+                config: reader.config(wire::CONFIG_VERSION)?,
+            },
+            KIND_REJECTED => Message::Rejected(reader.string("reason")?),
+            KIND_START => Message::Start(reader.varint()?),
+            KIND_FRAME => {
+                let tick = u32::try_from(reader.varint()?).map_err(|_| wire::Error::Invalid("tick"))?;
+                let count = reader.varint()?;
+                let mut turns = Vec::new();
+                for _ in 0..count {
+                    turns.push((reader.varint()? as usize, reader.direction()?));
+                }
+                Message::Frame { tick, turns }
+            }
+            _ => return Err(wire::Error::Invalid("message kind")),
+        };
+        Ok(message)
+    }
+}
+
+fn write_message(stream: &mut impl Write, message: &[u8]) -> io::Result<()> {
+    let mut bytes = Vec::with_capacity(message.len() + 4);
+    write_varint(&mut bytes, message.len() as u64);
+    bytes.extend(message);
+    stream.write_all(&bytes)
+}
+
+fn read_varint(stream: &mut impl Read) -> Result<u64, Error> {
+    let mut value = 0;
+    for shift in (0..64).step_by(7) {
+        let mut byte = [0];
+        stream.read_exact(&mut byte)?;
+        value |= ((byte[0] & 0x7f) as u64) << shift;
+        if byte[0] & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(Error::Broken(wire::Error::Invalid("number too long")))
+}
+
+fn read_message(stream: &mut impl Read) -> Result<Vec<u8>, Error> {
+    let length = read_varint(stream)?;
+    if length > MAX_MESSAGE_LENGTH {
+        return Err(Error::Broken(wire::Error::Invalid("message too long")));
+    }
+    let mut message = vec![0; length as usize];
+    stream.read_exact(&mut message)?;
+    Ok(message)
+}
+
+// How the server runs its games
+#[derive(Clone, Debug)]
+pub struct ServerConfig {
+    pub game: Config, // `players` is how many have to join before a round starts
+    pub ticks_per_second: f32,
+    pub input_delay: u32,      // In ticks
+    pub round_break: Duration, // Between the end of a round and the next one, so everyone sees how it ended
+    pub seed: u64,             // Every round gets its own seed from this one
+}
+
+impl Default for ServerConfig {
+    fn default() -> ServerConfig {
+        ServerConfig {
+            game: Config { players: 2, ..Config::default() },
+            ticks_per_second: 10.,
+            input_delay: 3,
+            round_break: Duration::from_secs(3),
+            seed: 0,
+        }
+    }
+}
+
+// What the connection threads tell the server. The id is there so a player that just left can't steer the snake of
+// whoever took their place
+enum Incoming {
+    // Someone said hello, the server decides if they get a snake
+    Hello { stream: TcpStream, address: SocketAddr, version: u64 },
+    // Someone connected but never said hello properly
+    Failed { address: SocketAddr, error: Error },
+    Turn { id: u64, player: usize, direction: Direction },
+    Left { id: u64, player: usize },
+}
+
+struct Connection {
+    id: u64,
+    stream: TcpStream,
+    address: SocketAddr,
+    outgoing: SyncSender<Vec<u8>>, // To the writer thread
+}
+
+pub struct Server {
+    listener: TcpListener,
+    config: ServerConfig,
+    seeds: SimRng,
+    connections: Vec<Option<Connection>>, // By player, None where nobody is connected
+    next_id: u64,
+    incoming: Receiver<Incoming>,
+    sender: Sender<Incoming>, // Cloned for every connection's threads
+    on_event: Box<dyn FnMut(ServerEvent) + Send>,
+}
+
+impl Server {
+    // Fails for games clients wouldn't accept, before anyone can join
+    pub fn bind(address: impl ToSocketAddrs, config: ServerConfig) -> io::Result<Server> {
+        let game = &config.game;
+        if game.players == 0 || game.players > MAX_PLAYERS || !game.has_room() {
+            let problem = format!("{} doesn't have room for {} players", game.level.name, game.players);
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, problem));
+        }
+        let listener = TcpListener::bind(address)?;
+        // Checked for new players between ticks, so it can't block
+        listener.set_nonblocking(true)?;
+        let (sender, incoming) = mpsc::channel();
+        Ok(Server {
+            listener,
+            seeds: support::seeded_rng(config.seed),
+            connections: (0..config.game.players).map(|_| None).collect(),
+            config,
+            next_id: 0,
+            incoming,
+            sender,
+            on_event: Box::new(|_| {}),
+        })
+    }
+
+    // Gets called with everything that happens from now on, nothing is shown otherwise. Runs on the server's thread,
+    // so it should be quick
+    pub fn on_event(&mut self, handler: impl FnMut(ServerEvent) + Send + 'static) {
+        self.on_event = Box::new(handler);
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    pub fn config(&self) -> &ServerConfig {
+        &self.config
+    }
+
+    pub fn connected(&self) -> usize {
+        self.connections.iter().flatten().count()
+    }
+
+    // Waits until every player is there, plays one round, then waits `round_break`. Returns the round so it can be
+    // saved or checked
+    pub fn run_round(&mut self) -> Recording {
+        while self.connected() < self.connections.len() {
+            self.accept();
+            self.take_turns();
+            thread::sleep(LOBBY_POLL);
+        }
+        let recording = self.play();
+        let end = Instant::now() + self.config.round_break;
+        while Instant::now() < end {
+            self.accept();
+            self.take_turns();
+            thread::sleep(LOBBY_POLL);
+        }
+        recording
+    }
+
+    fn play(&mut self) -> Recording {
+        let seed = self.seeds.gen();
+        let mut game = Game::new(&self.config.game, seed);
+        self.broadcast(&Message::Start(seed));
+
+        // Frames that were sent but not played yet, the first few don't have any turns because nobody could have
+        // turned that early
+        let delay = self.config.input_delay;
+        let mut frames: VecDeque<Turns> = VecDeque::new();
+        for tick in 0..delay {
+            self.broadcast(&Message::Frame { tick, turns: Vec::new() });
+            frames.push_back(Vec::new());
+        }
+
+        let tick_length = Duration::from_secs_f32(1. / self.config.ticks_per_second);
+        let mut next_tick = Instant::now();
+        while !game.is_over() {
+            self.accept();
+            let turns = self.take_turns();
+            self.broadcast(&Message::Frame { tick: game.ticks() + delay, turns: turns.clone() });
+            frames.push_back(turns);
+
+            for (player, direction) in frames.pop_front().unwrap_or_default() {
+                game.turn_player(player, direction);
+            }
+            game.tick();
+
+            // Going by when the tick should have happened instead of sleeping a whole tick, so slow ticks don't add up
+            next_tick += tick_length.div_f32(game.speed());
+            if let Some(wait) = next_tick.checked_duration_since(Instant::now()) {
+                thread::sleep(wait);
+            }
+        }
+        Recording::of(&game)
+    }
+
+    // Hands everyone who's knocking to a thread of their own to say hello, a slow client can't hold up the game that
+    // way. They're let in or turned away in `take_turns` once that's done
+    fn accept(&mut self) {
+        while let Ok((stream, address)) = self.listener.accept() {
+            let sender = self.sender.clone();
+            thread::spawn(move || {
+                let incoming = match read_hello(&stream) {
+                    Ok(version) => Incoming::Hello { stream, address, version },
+                    Err(error) => Incoming::Failed { address, error },
+                };
+                let _ = sender.send(incoming);
+            });
+        }
+    }
+
+    fn greet(&mut self, stream: TcpStream, address: SocketAddr, version: u64) -> Result<(), Error> {
+        let outgoing = start_writer(stream.try_clone()?);
+        let free = self.connections.iter().position(|connection| connection.is_none());
+        let player = match (version, free) {
+            (VERSION, Some(player)) => player,
+            (VERSION, None) => return reject(&outgoing, "the game is full".to_string()),
+            _ => {
+                let reason = format!("the server speaks version {}, this client speaks version {}", VERSION, version);
+                return reject(&outgoing, reason);
+            }
+        };
+
+        let welcome = Message::Welcome {
+            player,
+            tick_length: Duration::from_secs_f32(1. / self.config.ticks_per_second),
+            input_delay: self.config.input_delay,
+            config: self.config.game.clone(),
+        };
+        // The queue is empty, so this can't fail
+        let _ = outgoing.try_send(welcome.encode());
+        stream.set_read_timeout(None)?;
+
+        let id = self.next_id;
+        self.next_id += 1;
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let sender = self.sender.clone();
+        thread::spawn(move || loop {
+            let direction = read_message(&mut reader).and_then(|message| {
+                let mut reader = Reader { bytes: &message };
+                match reader.varint()? {
+                    KIND_TURN => Ok(reader.direction()?),
+                    _ => Err(Error::Broken(wire::Error::Invalid("message kind"))),
+                }
+            });
+            let incoming = match direction {
+                Ok(direction) => Incoming::Turn { id, player, direction },
+                Err(_) => Incoming::Left { id, player },
+            };
+            let left = matches!(incoming, Incoming::Left { .. });
+            // The server is gone if this fails
+            if sender.send(incoming).is_err() || left {
+                break;
+            }
+        });
+
+        (self.on_event)(ServerEvent::Joined { player, address });
+        self.connections[player] = Some(Connection { id, stream, address, outgoing });
+        Ok(())
+    }
+
+    // Everything the players sent since the last time, and who came and left
+    fn take_turns(&mut self) -> Turns {
+        let mut turns = Vec::new();
+        while let Ok(incoming) = self.incoming.try_recv() {
+            match incoming {
+                Incoming::Turn { id, player, direction }
+                    if self.is_connected(id, player)
+                        && turns.iter().filter(|(turned, _)| *turned == player).count() < MAX_TURNS_PER_FRAME =>
+                {
+                    turns.push((player, direction));
+                }
+                Incoming::Left { id, player } if self.is_connected(id, player) => self.disconnect(player),
+                Incoming::Hello { stream, address, version } => {
+                    if let Err(error) = self.greet(stream, address, version) {
+                        (self.on_event)(ServerEvent::Refused { address, error });
+                    }
+                }
+                Incoming::Failed { address, error } => (self.on_event)(ServerEvent::Refused { address, error }),
+                _ => {}
+            }
+        }
+        turns
+    }
+
+    fn is_connected(&self, id: u64, player: usize) -> bool {
+        self.connections[player].as_ref().is_some_and(|connection| connection.id == id)
+    }
+
+    // Dropping the connection also stops its writer thread
+    fn disconnect(&mut self, player: usize) {
+        if let Some(connection) = self.connections[player].take() {
+            let _ = connection.stream.shutdown(Shutdown::Both);
+            (self.on_event)(ServerEvent::Left { player, address: connection.address });
+        }
+    }
+
+    // Only queues the message for every writer thread, so it never waits on the network. Whoever has too much
+    // waiting already is too slow to play and gets dropped
+    fn broadcast(&mut self, message: &Message) {
+        let bytes = message.encode();
+        for player in 0..self.connections.len() {
+            let failed = match &self.connections[player] {
+                Some(connection) => connection.outgoing.try_send(bytes.clone()).is_err(),
+                None => false,
+            };
+            if failed {
+                self.disconnect(player);
+            }
+        }
+    }
+}
+
+// The magic and the protocol version, blocking until they're there or the handshake takes too long
+fn read_hello(mut stream: &TcpStream) -> Result<u64, Error> {
+    stream.set_nonblocking(false)?;
+    stream.set_nodelay(true)?;
+    stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+
+    let mut magic = [0; 4];
+    stream.read_exact(&mut magic)?;
+    if &magic != MAGIC {
+        return Err(Error::Broken(wire::Error::Invalid("not a snake client")));
+    }
+    read_varint(&mut stream)
+}
+
+// Writes whatever gets queued for the other side on its own thread, so the game never waits on the network. When the
+// queue is dropped (the player left, or the server or client is gone) it sends what's left and closes the connection,
+// which also ends the reader thread
+fn start_writer(mut stream: TcpStream) -> SyncSender<Vec<u8>> {
+    let (outgoing, messages) = mpsc::sync_channel::<Vec<u8>>(MAX_UNSENT);
+    thread::spawn(move || {
+        for message in messages {
+            if write_message(&mut stream, &message).is_err() {
+                break;
+            }
+        }
+        let _ = stream.shutdown(Shutdown::Both);
+    });
+    outgoing
+}
+
+fn reject(outgoing: &SyncSender<Vec<u8>>, reason: String) -> Result<(), Error> {
+    let _ = outgoing.try_send(Message::Rejected(reason.clone()).encode());
+    Err(Error::Rejected(reason))
+}
+
+// One player's side of a networked game
+pub struct Client {
+    stream: TcpStream,
+    outgoing: SyncSender<Vec<u8>>, // To the writer thread
+    messages: Receiver<Result<Message, Error>>,
+    received: VecDeque<Result<Message, Error>>, // Read but not handled yet, see `receive`
+    player: usize,
+    config: Config,
+    tick_length: Duration,
+    input_delay: u32,
+    game: Option<Game>,      // None until the first round starts
+    frames: VecDeque<Turns>, // Received but not played yet, the first one is for the next tick
+}
+
+impl Client {
+    pub fn connect(address: impl ToSocketAddrs) -> Result<Client, Error> {
+        let mut stream = connect(address)?;
+        stream.set_nodelay(true)?;
+        stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+        stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+
+        let mut hello = MAGIC.to_vec();
+        write_varint(&mut hello, VERSION);
+        stream.write_all(&hello)?;
+        let (player, tick_length, input_delay, config) = match Message::decode(&read_message(&mut stream)?)? {
+            Message::Welcome { player, tick_length, input_delay, config } => (player, tick_length, input_delay, config),
+            Message::Rejected(reason) => return Err(Error::Rejected(reason)),
+            _ => return Err(Error::Broken(wire::Error::Invalid("expected a welcome"))),
+        };
+        // Every player has to have a snake, ours included
+        if player >= config.players || !config.has_room() {
+            return Err(Error::Broken(wire::Error::Invalid("player")));
+        }
+        stream.set_read_timeout(None)?;
+
+        // Reading blocks, so it happens on its own thread and everything read waits in the channel
+        let (sender, messages) = mpsc::channel();
+        let mut reader = BufReader::new(stream.try_clone()?);
+        thread::spawn(move || loop {
+            let message = read_message(&mut reader).and_then(|message| Ok(Message::decode(&message)?));
+            let failed = message.is_err();
+            if sender.send(message).is_err() || failed {
+                break;
+            }
+        });
+
+        Ok(Client {
+            outgoing: start_writer(stream.try_clone()?),
+            stream,
+            messages,
+            received: VecDeque::new(),
+            player,
+            config,
+            tick_length,
+            input_delay,
+            game: None,
+            frames: VecDeque::new(),
+        })
+    }
+
+    // Which snake is ours
+    pub fn player(&self) -> usize {
+        self.player
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn ticks_per_second(&self) -> f32 {
+        1. / self.tick_length.as_secs_f32()
+    }
+
+    pub fn input_delay(&self) -> u32 {
+        self.input_delay
+    }
+
+    // The round being played, or the last one while waiting for the next
+    pub fn game(&self) -> Option<&Game> {
+        self.game.as_ref()
+    }
+
+    // Frames that arrived but weren't played yet
+    pub fn buffered(&self) -> usize {
+        self.frames.len()
+    }
+
+    // Sends a turn to the server, it happens `input_delay` ticks from now (plus the lag). Only while a round is on,
+    // otherwise there's no snake of ours to turn. It's only queued, a server that stopped taking turns is an error
+    // once the queue is full
+    pub fn turn(&mut self, direction: Direction) -> Result<(), Error> {
+        if self.game.as_ref().is_none_or(|game| game.is_over()) {
+            return Ok(());
+        }
+        let mut message = Vec::new();
+        write_varint(&mut message, KIND_TURN);
+        write_varint(&mut message, direction_code(direction));
+        match self.outgoing.try_send(message) {
+            Ok(()) => Ok(()),
+            Err(TrySendError::Full(_)) => {
+                Err(Error::Io(io::Error::new(io::ErrorKind::TimedOut, "the server stopped taking turns")))
+            }
+            Err(TrySendError::Disconnected(_)) => Err(Error::Closed),
+        }
+    }
+
+    // Handles everything the server sent since the last call, without waiting for more. The end of a round and the
+    // start of the next one can come in together when we're lagging behind, so everything from the start on waits
+    // until the round was played to the end. Same for the connection closing, that's only an error once the frames
+    // that came before it are played
+    pub fn receive(&mut self) -> Result<(), Error> {
+        loop {
+            match self.messages.try_recv() {
+                Ok(message) => self.received.push_back(message),
+                Err(TryRecvError::Empty) => break,
+                // The reader always sends why it stopped first, so this is only reached once that was handled
+                Err(TryRecvError::Disconnected) if self.received.is_empty() => return Err(Error::Closed),
+                Err(TryRecvError::Disconnected) => break,
+            }
+        }
+        while let Some(next) = self.received.front() {
+            let waits = matches!(next, Ok(Message::Start(_)) | Err(_)) && self.round_left();
+            if waits {
+                return Ok(());
+            }
+            let message = match self.received.pop_front() {
+                Some(message) => message?,
+                None => break,
+            };
+            match message {
+                Message::Start(seed) => {
+                    self.game = Some(Game::new(&self.config, seed));
+                    self.frames.clear();
+                }
+                // Frames come in order, so the tick is only needed to notice if the server got something wrong
+                Message::Frame { tick, turns } => {
+                    let expected = self.game.as_ref().map(|game| game.ticks() as usize + self.frames.len());
+                    match expected {
+                        Some(expected) if expected == tick as usize => self.frames.push_back(turns),
+                        Some(_) => return Err(Error::Broken(wire::Error::Invalid("frame out of order"))),
+                        // Joined in the middle of a round, the frames are for a game we don't have
+                        None => {}
+                    }
+                }
+                Message::Welcome { .. } | Message::Rejected(_) => {
+                    return Err(Error::Broken(wire::Error::Invalid("unexpected message")));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // If the round isn't over yet and there are frames to play it further
+    fn round_left(&self) -> bool {
+        self.game.as_ref().is_some_and(|game| !game.is_over()) && !self.frames.is_empty()
+    }
+
+    // Plays the next tick if its frame is there, returns if it did
+    pub fn tick(&mut self) -> bool {
+        let game = match &mut self.game {
+            Some(game) if !game.is_over() => game,
+            _ => return false,
+        };
+        let turns = match self.frames.pop_front() {
+            Some(turns) => turns,
+            None => return false,
+        };
+        for (player, direction) in turns {
+            game.turn_player(player, direction);
+        }
+        game.tick();
+        true
+    }
+
+    // For front-ends with their own clock: receives, then plays `ticks` ticks as far as the frames go. If more frames
+    // piled up than the input delay accounts for (the window was busy, or the clocks drift apart) it catches up
+    // right away. Returns how many ticks were played
+    pub fn update(&mut self, ticks: u32) -> Result<u32, Error> {
+        self.receive()?;
+        let mut played = 0;
+        while (played < ticks || self.frames.len() > self.input_delay as usize + 1) && self.tick() {
+            played += 1;
+        }
+        Ok(played)
+    }
+}
+
+impl Drop for Client {
+    // The reader and writer threads have their own handles on the connection, without this it'd stay open
+    fn drop(&mut self) {
+        let _ = self.stream.shutdown(Shutdown::Both);
+    }
+}
+
+// Like `TcpStream::connect`, but gives up after a while instead of hanging when nobody answers
+fn connect(address: impl ToSocketAddrs) -> Result<TcpStream, Error> {
+    let mut last_err = None;
+    for address in address.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&address, HANDSHAKE_TIMEOUT) {
+            Ok(stream) => return Ok(stream),
+            Err(err) => last_err = Some(err),
+        }
+    }
+    let err = last_err.unwrap_or_else(|| io::Error::new(io::ErrorKind::NotFound, "the address didn't resolve"));
+    Err(Error::from(err))
+}
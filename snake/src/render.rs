@@ -0,0 +1,145 @@
+// Snakes drawn as one rounded body instead of a row of squares. The body goes through the middle of every segment,
+// gets thinner at the tail and darker towards it, and the head has eyes looking where it's going. Food that was just
+// eaten shows up as a bulge that goes down the body
+
+use nannou::prelude::*;
+
+use crate::game::{Game, Snake};
+use crate::grid::{Boundary, Direction, Grid};
+use crate::layout::Layout;
+use crate::lerp_axis;
+
+// Sizes compared to a cell
+const BODY_WIDTH: f32 = 0.7;
+const HEAD_SIZE: f32 = 0.85;
+const EYE_SIZE: f32 = 0.26;
+const PUPIL_SIZE: f32 = 0.13;
+// The body gets thinner over this many segments at the end, down to this much of its width at the tip of the tail
+const TAPER_SEGMENTS: f32 = 4.;
+const TAIL_WIDTH: f32 = 0.3;
+// Brightness at the tail compared to the head
+const TAIL_BRIGHTNESS: f32 = 0.45;
+// How much wider than the body food going down it is
+const BULGE: f32 = 0.4;
+
+// Points the body goes through from the head to the tail, in cells. The head and the tail are between the last two
+// ticks and the rest are the segments. In wrap mode points are moved across the edge so the path doesn't jump, so some
+// can be off the board
+fn path(snake: &Snake, grid: Grid, boundary: Boundary, alpha: f32) -> Vec<Vec2> {
+    let cells: Vec<_> = snake.body().collect();
+    let lerp = |index: usize| {
+        let (from, to) = (snake.previous(index), cells[index]);
+        vec2(lerp_axis(from.x, to.x, grid.width, alpha), lerp_axis(from.y, to.y, grid.height, alpha))
+    };
+    let mut points = vec![lerp(0)];
+    points.extend(cells.iter().skip(1).map(|cell| vec2(cell.x as f32, cell.y as f32)));
+    points.push(lerp(cells.len() - 1));
+
+    if boundary == Boundary::Wrap {
+        // Points next to each other are at most a cell apart, anything further went through the edge
+        let across = |delta: f32, size: i32| {
+            if delta.abs() > 1. {
+                (delta / size as f32).round() * size as f32
+            } else {
+                0.
+            }
+        };
+        for i in 1..points.len() {
+            let previous = points[i - 1];
+            let point = &mut points[i];
+            point.x -= across(point.x - previous.x, grid.width);
+            point.y -= across(point.y - previous.y, grid.height);
+        }
+    }
+    points
+}
+
+// Whole boards a path from `min` to `max` has to be moved by to cover every part of the board it shows up on
+fn wrap_offsets(min: f32, max: f32, size: i32) -> Vec<f32> {
+    let size = size as f32;
+    let first = ((-1. - max) / size).floor() as i32 + 1;
+    let last = ((size - min) / size).ceil() as i32 - 1;
+    (first..=last).map(|board| board as f32 * size).collect()
+}
+
+// `distances` is how far along the path every point is
+fn point_at(points: &[Vec2], distances: &[f32], distance: f32) -> Vec2 {
+    let next = distances.iter().position(|d| *d >= distance).unwrap_or(points.len() - 1).max(1);
+    let length = distances[next] - distances[next - 1];
+    let t = if length > 0. { (distance - distances[next - 1]) / length } else { 0. };
+    points[next - 1].lerp(points[next], t)
+}
+
+fn direction_vector(direction: Direction) -> Vec2 {
+    match direction {
+        Direction::Up => vec2(0., 1.),
+        Direction::Down => vec2(0., -1.),
+        Direction::Left => vec2(-1., 0.),
+        Direction::Right => vec2(1., 0.),
+    }
+}
+
+pub fn draw_snake(draw: &Draw, layout: &Layout, game: &Game, snake: &Snake, color: Srgb<u8>, alpha: f32) {
+    let grid = game.grid();
+    let points = path(snake, grid, game.boundary(), alpha);
+    let mut distances = vec![0.];
+    for pair in points.windows(2) {
+        distances.push(distances[distances.len() - 1] + pair[0].distance(pair[1]));
+    }
+    let total = distances[distances.len() - 1];
+
+    let head_color: Srgb = color.into_format();
+    let color_at = |distance: f32| {
+        let brightness = 1. - (1. - TAIL_BRIGHTNESS) * if total > 0. { distance / total } else { 0. };
+        rgb(head_color.red * brightness, head_color.green * brightness, head_color.blue * brightness)
+    };
+    let width_at = |distance: f32| {
+        let taper = ((total - distance) / TAPER_SEGMENTS).min(1.);
+        BODY_WIDTH * (TAIL_WIDTH + (1. - TAIL_WIDTH) * taper)
+    };
+
+    // The food is in the cell the head went into on the tick it was eaten, and the head is drawn a tick behind, so
+    // it reaches the food right as `ticks_since_food` is 0 and `alpha` is 1
+    let bulge = (snake.eaten() > 0).then(|| snake.ticks_since_food() as f32 + alpha - 1.);
+
+    // Looking the way it's moving, that turns smoothly around corners
+    let forward = (points[0] - points[1]).try_normalize().unwrap_or_else(|| direction_vector(snake.direction()));
+    let side = vec2(-forward.y, forward.x);
+
+    let size = layout.cell_size();
+    let (min, max) = points.iter().fold((points[0], points[0]), |(min, max), point| (min.min(*point), max.max(*point)));
+    for offset_x in wrap_offsets(min.x, max.x, grid.width) {
+        for offset_y in wrap_offsets(min.y, max.y, grid.height) {
+            let offset = vec2(offset_x, offset_y);
+            let to_screen = |point: Vec2| layout.to_screen(point.x + offset.x, point.y + offset.y);
+
+            // Tail first so the parts closer to the head go on top
+            for i in (1..points.len()).rev() {
+                let (start, end) = (to_screen(points[i - 1]), to_screen(points[i]));
+                let width = width_at(distances[i]) * size;
+                let color = color_at(distances[i - 1]);
+                if start != end {
+                    draw.line().start(start).end(end).weight(width).caps_round().color(color);
+                }
+                draw.ellipse().xy(end).w_h(width, width).color(color);
+            }
+            if let Some(bulge) = bulge.filter(|bulge| (0. ..=total).contains(bulge)) {
+                let width = width_at(bulge) * (1. + BULGE) * size;
+                let point = point_at(&points, &distances, bulge);
+                draw.ellipse().xy(to_screen(point)).w_h(width, width).color(color_at(bulge));
+            }
+
+            // Gulps a bit as it gets to the food
+            let gulp = bulge.map_or(0., |bulge| BULGE * (1. - bulge.abs()).max(0.));
+            let head = to_screen(points[0]);
+            let head_size = HEAD_SIZE * (1. + gulp) * size;
+            draw.ellipse().xy(head).w_h(head_size, head_size).color(color_at(0.));
+            for eye_side in [-1., 1.] {
+                let eye = head + (forward * 0.15 + side * eye_side * 0.2) * size;
+                draw.ellipse().xy(eye).w_h(EYE_SIZE * size, EYE_SIZE * size).color(WHITE);
+                let pupil = eye + forward * 0.05 * size;
+                draw.ellipse().xy(pupil).w_h(PUPIL_SIZE * size, PUPIL_SIZE * size).color(BLACK);
+            }
+        }
+    }
+}
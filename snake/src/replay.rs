@@ -0,0 +1,390 @@
+// A game is saved as its config, seed and everything the players did with the tick it happened on. The game only
+// uses its own seeded rng, so that's enough to play it again exactly the same.
+//
+// Files are binary to keep them small (a few bytes per turn), all numbers are LEB128 varints:
+//
+//   "SNKR" version
+//   seed ticks player-count score...
+//   config (see `wire`)
+//   event-count (tick-delta << 3 | kind [food-count | player direction])...
+//
+// where kind is 0-3 for the first player turning up, down, left or right, 4 for a change of the food count followed by
+// the count and 5 for another player turning, followed by the player and the direction. Version 1 files only had one
+// player, so there's a single score and no player count. The config has the same version as the file
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::game::{Config, Game};
+use crate::grid::Direction;
+use crate::wire::{self, direction_code, write_config, write_varint, Reader};
+
+const MAGIC: &[u8; 4] = b"SNKR";
+// Bump this when the layout changes, and teach `Recording::decode` to read the old one
+const VERSION: u64 = 3;
+
+const KIND_FOOD_COUNT: u64 = 4;
+const KIND_OTHER_PLAYER: u64 = 5;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EventKind {
+    Turn(usize, Direction), // Player and where they turned
+    FoodCount(usize),
+}
+
+// Something a player did, `tick` is how many ticks were played before it
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Event {
+    pub tick: u32,
+    pub kind: EventKind,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    NotAReplay,
+    UnsupportedVersion(u64),
+    Truncated,
+    Invalid(&'static str),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "couldn't read or write the replay: {}", err),
+            Error::NotAReplay => write!(f, "this isn't a snake replay"),
+            Error::UnsupportedVersion(version) => {
+                write!(f, "the replay has version {}, this game only knows up to {}", version, VERSION)
+            }
+            Error::Truncated => write!(f, "the replay ends in the middle, it might not have been saved completely"),
+            Error::Invalid(what) => write!(f, "the replay is broken: {}", what),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Error {
+        Error::Io(err)
+    }
+}
+
+impl From<wire::Error> for Error {
+    fn from(err: wire::Error) -> Error {
+        match err {
+            wire::Error::Truncated => Error::Truncated,
+            wire::Error::Invalid(what) => Error::Invalid(what),
+        }
+    }
+}
+
+// Everything needed to play a finished (or not finished) game again
+#[derive(Clone, Debug)]
+pub struct Recording {
+    pub config: Config,
+    pub seed: u64,
+    pub events: Vec<Event>,
+    pub ticks: u32,       // How long the game went
+    pub scores: Vec<u32>, // What every player ended with, to check the replay against
+}
+
+impl Recording {
+    // What's been played of `game` so far
+    pub fn of(game: &Game) -> Recording {
+        Recording {
+            config: game.config().clone(),
+            seed: game.seed(),
+            events: game.events().to_vec(),
+            ticks: game.ticks(),
+            scores: game.snakes().iter().map(|snake| snake.score()).collect(),
+        }
+    }
+
+    // Where saved replays go, somewhere in the user's data dir (~/.local/share on linux)
+    pub fn default_dir() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("nannou-projects").join("snake_replays"))
+    }
+
+    // The newest `.snkr` file in `dir`, going by name since they're named after the time they were saved
+    pub fn newest_in(dir: &Path) -> Option<PathBuf> {
+        fs::read_dir(dir)
+            .ok()?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|extension| extension == "snkr"))
+            .max()
+    }
+
+    pub fn load(path: &Path) -> Result<Recording, Error> {
+        Recording::decode(&fs::read(path)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.encode())?;
+        Ok(())
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        write_varint(&mut bytes, VERSION);
+        write_varint(&mut bytes, self.seed);
+        write_varint(&mut bytes, self.ticks as u64);
+        write_varint(&mut bytes, self.scores.len() as u64);
+        for score in &self.scores {
+            write_varint(&mut bytes, *score as u64);
+        }
+
+        write_config(&mut bytes, &self.config);
+
+        write_varint(&mut bytes, self.events.len() as u64);
+        let mut last_tick = 0;
+        for event in &self.events {
+            let delta = (event.tick - last_tick) as u64;
+            last_tick = event.tick;
+            match event.kind {
+                EventKind::Turn(0, direction) => write_varint(&mut bytes, delta << 3 | direction_code(direction)),
+                EventKind::Turn(player, direction) => {
+                    write_varint(&mut bytes, delta << 3 | KIND_OTHER_PLAYER);
+                    write_varint(&mut bytes, player as u64);
+                    write_varint(&mut bytes, direction_code(direction));
+                }
+                EventKind::FoodCount(count) => {
+                    write_varint(&mut bytes, delta << 3 | KIND_FOOD_COUNT);
+                    write_varint(&mut bytes, count as u64);
+                }
+            }
+        }
+        bytes
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Recording, Error> {
+        let mut reader = Reader { bytes };
+        if reader.bytes(MAGIC.len()).map_err(|_| Error::NotAReplay)? != MAGIC {
+            return Err(Error::NotAReplay);
+        }
+        let version = reader.varint()?;
+        // Versions start at 1
+        if version == 0 || version > VERSION {
+            return Err(Error::UnsupportedVersion(version));
+        }
+        let seed = reader.varint()?;
+        let ticks = u32::try_from(reader.varint()?).map_err(|_| Error::Invalid("too many ticks"))?;
+        let score_count = if version >= 2 { reader.varint()? } else { 1 };
+        let mut scores = Vec::new();
+        for _ in 0..score_count {
+            scores.push(u32::try_from(reader.varint()?).map_err(|_| Error::Invalid("score too high"))?);
+        }
+
+        let config = reader.config(version)?;
+
+        let event_count = reader.varint()?;
+        let mut events = Vec::new();
+        let mut tick = 0u32;
+        for _ in 0..event_count {
+            let value = reader.varint()?;
+            tick = u32::try_from(tick as u64 + (value >> 3)).map_err(|_| Error::Invalid("too many ticks"))?;
+            let kind = match value & 0b111 {
+                KIND_FOOD_COUNT => {
+                    // Same limit as the count the game starts with
+                    let count = reader.varint()?;
+                    if count > config.level.max_food() as u64 {
+                        return Err(Error::Invalid("food count"));
+                    }
+                    EventKind::FoodCount(count as usize)
+                }
+                KIND_OTHER_PLAYER => {
+                    let player = reader.varint()? as usize;
+                    EventKind::Turn(player, reader.direction()?)
+                }
+                code => EventKind::Turn(0, wire::direction_from_code(code).ok_or(Error::Invalid("event"))?),
+            };
+            events.push(Event { tick, kind });
+        }
+
+        Ok(Recording { config, seed, events, ticks, scores })
+    }
+}
+
+// Plays a recording back one tick at a time
+pub struct Player {
+    recording: Recording,
+    game: Game,
+    next_event: usize,
+}
+
+impl Player {
+    pub fn new(recording: Recording) -> Player {
+        let game = Game::new(&recording.config, recording.seed);
+        Player { recording, game, next_event: 0 }
+    }
+
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    // Once it's played as many ticks as the recorded game had
+    pub fn is_finished(&self) -> bool {
+        self.game.ticks() >= self.recording.ticks || self.game.is_over()
+    }
+
+    // Does what the player did before this tick, then the tick itself
+    pub fn tick(&mut self) {
+        if self.is_finished() {
+            return;
+        }
+        while let Some(event) = self.recording.events.get(self.next_event) {
+            if event.tick > self.game.ticks() {
+                break;
+            }
+            match event.kind {
+                EventKind::Turn(player, direction) => {
+                    self.game.turn_player(player, direction);
+                }
+                EventKind::FoodCount(count) => self.game.set_food_count(count),
+            }
+            self.next_event += 1;
+        }
+        self.game.tick();
+    }
+
+    // Plays the rest of it at once
+    pub fn finish(&mut self) {
+        while !self.is_finished() {
+            self.tick();
+        }
+    }
+
+    pub fn restart(&mut self) {
+        self.game = Game::new(&self.recording.config, self.recording.seed);
+        self.next_event = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::{ShortestPath, SnakeController};
+    use crate::food;
+    use crate::grid::Boundary;
+    use crate::level::Level;
+
+    // Old files have to keep playing the same. Both are ShortestPath games saved with `Recording::save` from the commit
+    // that introduced their version: v1 at 1ceec84 (Pillars, wrapping, 2 food going to 3 at tick 50, seed 1234, stopped
+    // at tick 400), v2 at 18758d5 (Box, two players, 2 food, seed 5678, played until it was over)
+    const V1: &[u8] = include_bytes!("../tests/replays/v1.snkr");
+    const V2: &[u8] = include_bytes!("../tests/replays/v2.snkr");
+
+    // Two bots on a level with walls, with every kind of event
+    fn recording() -> Recording {
+        let level = Level::find("pillars").unwrap();
+        let config = Config { level, boundary: Boundary::Wrap, food_count: 2, players: 2, ..Config::default() };
+        let mut game = Game::new(&config, 42);
+        let mut bot = ShortestPath;
+        while !game.is_over() && game.ticks() < 300 {
+            if game.ticks() == 5 {
+                game.set_food_count(4);
+            }
+            for player in 0..2 {
+                let direction = bot.decide(&game, player);
+                game.turn_player(player, direction);
+            }
+            game.tick();
+        }
+        Recording::of(&game)
+    }
+
+    fn replayed_scores(recording: &Recording) -> Vec<u32> {
+        let mut player = Player::new(recording.clone());
+        player.finish();
+        player.game().snakes().iter().map(|snake| snake.score()).collect()
+    }
+
+    #[test]
+    fn round_trip() {
+        let recording = recording();
+        assert!(recording.events.iter().any(|event| matches!(event.kind, EventKind::Turn(1, _))));
+        assert!(recording.events.iter().any(|event| matches!(event.kind, EventKind::FoodCount(4))));
+
+        let bytes = recording.encode();
+        let decoded = Recording::decode(&bytes).unwrap();
+        assert_eq!(decoded.seed, recording.seed);
+        assert_eq!(decoded.ticks, recording.ticks);
+        assert_eq!(decoded.scores, recording.scores);
+        assert_eq!(decoded.events, recording.events);
+        assert_eq!(decoded.config.level.walls, recording.config.level.walls);
+        assert_eq!(decoded.config.boundary, Boundary::Wrap);
+        assert_eq!(decoded.config.players, 2);
+        assert_eq!(decoded.config.food_weights, food::DEFAULT_WEIGHTS);
+        assert_eq!(decoded.encode(), bytes);
+        assert_eq!(replayed_scores(&decoded), recording.scores);
+    }
+
+    #[test]
+    fn reads_version_1() {
+        let recording = Recording::decode(V1).unwrap();
+        assert_eq!(recording.config.level.name, "Pillars");
+        assert_eq!(recording.config.boundary, Boundary::Wrap);
+        assert_eq!(recording.config.players, 1);
+        assert_eq!(recording.config.food_weights, food::CLASSIC_WEIGHTS);
+        assert_eq!((recording.seed, recording.ticks, recording.scores.clone()), (1234, 400, vec![927]));
+        assert!(recording.events.iter().any(|event| event.kind == EventKind::FoodCount(3)));
+        assert_eq!(replayed_scores(&recording), recording.scores);
+    }
+
+    #[test]
+    fn reads_version_2() {
+        let recording = Recording::decode(V2).unwrap();
+        assert_eq!(recording.config.level.name, "Box");
+        assert_eq!(recording.config.players, 2);
+        assert_eq!(recording.config.food_weights, food::CLASSIC_WEIGHTS);
+        assert_eq!((recording.seed, recording.ticks, recording.scores.clone()), (5678, 269, vec![198, 245]));
+        assert_eq!(replayed_scores(&recording), recording.scores);
+    }
+
+    #[test]
+    fn cut_off_files_are_truncated() {
+        for bytes in [recording().encode(), V1.to_vec(), V2.to_vec()] {
+            for length in MAGIC.len()..bytes.len() {
+                assert!(matches!(Recording::decode(&bytes[..length]), Err(Error::Truncated)), "cut at {}", length);
+            }
+        }
+    }
+
+    #[test]
+    fn broken_files() {
+        assert!(matches!(Recording::decode(b"PNG\x0d\x0a"), Err(Error::NotAReplay)));
+        assert!(matches!(Recording::decode(b"SN"), Err(Error::NotAReplay)));
+
+        let mut newer = MAGIC.to_vec();
+        write_varint(&mut newer, VERSION + 1);
+        assert!(matches!(Recording::decode(&newer), Err(Error::UnsupportedVersion(version)) if version == VERSION + 1));
+        let mut zero = MAGIC.to_vec();
+        write_varint(&mut zero, 0);
+        assert!(matches!(Recording::decode(&zero), Err(Error::UnsupportedVersion(0))));
+
+        // The last event of version 1 files is a turn, 6 and 7 aren't kinds of events
+        let mut bad_event = V1.to_vec();
+        *bad_event.last_mut().unwrap() |= 0b111;
+        assert!(matches!(Recording::decode(&bad_event), Err(Error::Invalid("event"))));
+
+        // More food than the level has room for
+        let mut too_much_food = recording();
+        let (tick, count) = (too_much_food.ticks, too_much_food.config.level.max_food() + 1);
+        too_much_food.events.push(Event { tick, kind: EventKind::FoodCount(count) });
+        assert!(matches!(Recording::decode(&too_much_food.encode()), Err(Error::Invalid("food count"))));
+
+        // Right after the magic, version and seed is the tick count, which doesn't fit in a u32 here
+        let mut too_long = MAGIC.to_vec();
+        write_varint(&mut too_long, VERSION);
+        write_varint(&mut too_long, 1);
+        write_varint(&mut too_long, u64::MAX);
+        assert!(matches!(Recording::decode(&too_long), Err(Error::Invalid("too many ticks"))));
+    }
+}
@@ -0,0 +1,142 @@
+use rand::Rng;
+use support::SimRng;
+
+use crate::food::Weights;
+use crate::game::{Config, Game};
+use crate::grid::{Boundary, Direction};
+use crate::level::Level;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum State {
+    Title,
+    Playing,
+    Paused,
+    GameOver,
+    HighScores, // The high score table, reachable from the title screen
+}
+
+// Everything around a single game: the title screen, pausing and restarting. Front-ends turn their input into calls
+// on this and draw whatever the state is
+pub struct Session {
+    state: State,
+    config: Config,
+    game: Game,
+    seeds: SimRng, // Every game gets its own seed from here, so a whole session can be replayed from one seed
+}
+
+impl Session {
+    pub fn new(config: Config, seed: u64) -> Session {
+        let mut seeds = support::seeded_rng(seed);
+        let game = Game::new(&config, seeds.gen());
+        Session { state: State::Title, config, game, seeds }
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    pub fn game_mut(&mut self) -> &mut Game {
+        &mut self.game
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    // The config is for the next game, the current one keeps the config it started with. On the title screen the
+    // game waiting there gets swapped for one with the new config right away
+    pub fn set_level(&mut self, level: Level) {
+        self.config.level = level;
+        self.replace_title_game();
+    }
+
+    // Same as `set_level`
+    pub fn set_boundary(&mut self, boundary: Boundary) {
+        self.config.boundary = boundary;
+        self.replace_title_game();
+    }
+
+    // Same as `set_level`
+    pub fn set_players(&mut self, players: usize) {
+        self.config.players = players;
+        self.replace_title_game();
+    }
+
+    // Same as `set_level`
+    pub fn set_food_count(&mut self, food_count: usize) {
+        self.config.food_count = food_count;
+        self.replace_title_game();
+    }
+
+    // Same as `set_level`
+    pub fn set_food_weights(&mut self, food_weights: Weights) {
+        self.config.food_weights = food_weights;
+        self.replace_title_game();
+    }
+
+    fn replace_title_game(&mut self) {
+        if self.state == State::Title {
+            self.game = Game::new(&self.config, self.seeds.gen());
+        }
+    }
+
+    // Title -> Playing
+    pub fn start(&mut self) {
+        if self.state == State::Title {
+            self.state = State::Playing;
+        }
+    }
+
+    // Title or GameOver -> HighScores
+    pub fn show_high_scores(&mut self) {
+        if matches!(self.state, State::Title | State::GameOver) {
+            self.state = State::HighScores;
+        }
+    }
+
+    // Playing <-> Paused
+    pub fn toggle_pause(&mut self) {
+        match self.state {
+            State::Playing => self.state = State::Paused,
+            State::Paused => self.state = State::Playing,
+            _ => {}
+        }
+    }
+
+    // Throws the current game away and starts a new one with a new snake and food
+    pub fn restart(&mut self) {
+        self.game = Game::new(&self.config, self.seeds.gen());
+        self.state = State::Playing;
+    }
+
+    // Back to the title screen, with a fresh game ready for when it gets started
+    pub fn back_to_title(&mut self) {
+        self.game = Game::new(&self.config, self.seeds.gen());
+        self.state = State::Title;
+    }
+
+    pub fn turn(&mut self, direction: Direction) {
+        self.turn_player(0, direction);
+    }
+
+    pub fn turn_player(&mut self, player: usize, direction: Direction) {
+        if self.state == State::Playing {
+            self.game.turn_player(player, direction);
+        }
+    }
+
+    // The game only moves while playing
+    pub fn tick(&mut self) {
+        if self.state != State::Playing {
+            return;
+        }
+        self.game.tick();
+        if self.game.is_over() {
+            self.state = State::GameOver;
+        }
+    }
+}
@@ -0,0 +1,42 @@
+// Turns the variable time between frames into a fixed amount of game ticks, so the game runs at the same speed
+// no matter how fast the monitor refreshes
+pub struct Ticker {
+    pub ticks_per_second: f32,
+    accumulator: f32, // Time that passed but wasn't used up by a tick yet, in seconds
+}
+
+// If a frame took really long (window getting dragged, breakpoints) we drop the time instead of running a
+// burst of ticks the player can't react to
+const MAX_TICKS_PER_ADVANCE: u32 = 5;
+
+impl Ticker {
+    pub fn new(ticks_per_second: f32) -> Ticker {
+        Ticker { ticks_per_second, accumulator: 0. }
+    }
+
+    pub fn tick_length(&self) -> f32 {
+        1. / self.ticks_per_second
+    }
+
+    // Adds `dt` seconds and returns how many ticks have to run because of it
+    pub fn advance(&mut self, dt: f32) -> u32 {
+        let tick_length = self.tick_length();
+        self.accumulator += dt;
+
+        let mut ticks = 0;
+        while self.accumulator >= tick_length {
+            self.accumulator -= tick_length;
+            ticks += 1;
+            if ticks == MAX_TICKS_PER_ADVANCE {
+                self.accumulator = 0.;
+                break;
+            }
+        }
+        ticks
+    }
+
+    // How far we are between the last tick and the next one, from 0 to 1. Used to interpolate drawing
+    pub fn alpha(&self) -> f32 {
+        (self.accumulator / self.tick_length()).min(1.)
+    }
+}
@@ -0,0 +1,373 @@
+// Plays snake in a terminal instead of a window, so it can be played (or the bots watched) over SSH. It's the same
+// session and game as the window, drawn with box drawing characters and steered with keys read in raw mode
+
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Color, Print, ResetColor, Stylize};
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, queue};
+use support::Options;
+
+use crate::difficulty::Difficulty;
+use crate::driver::Driver;
+use crate::food::FoodKind;
+use crate::game::Game;
+use crate::grid::{Boundary, Direction};
+use crate::session::State;
+use crate::{effects_line, food_blinked_out, game_over_title, load_levels, scores_line, PLAYER_NAMES};
+
+// Terminals don't need to be redrawn as often as windows, and it's less to send over SSH
+const FRAME_LENGTH: Duration = Duration::from_millis(33);
+
+// Head and body color of every player, close to the colors in the window
+const PLAYER_COLORS: [(Color, Color); 2] = [(Color::White, Color::Grey), (Color::Cyan, Color::DarkCyan)];
+
+// Lines above and below the board, for the HUD and what's going on. The help goes under those if there's room
+const EXTRA_LINES: u16 = 2;
+
+// What's in a cell, as far as drawing it goes
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Tile {
+    Empty,
+    Wall,
+    Food(FoodKind),
+    Head(usize),
+    Body(usize),
+}
+
+impl Tile {
+    fn color(self) -> Color {
+        match self {
+            Tile::Empty => Color::Reset,
+            Tile::Wall => Color::DarkGrey,
+            Tile::Food(FoodKind::Normal) => Color::Red,
+            Tile::Food(FoodKind::Big) => Color::DarkYellow,
+            Tile::Food(FoodKind::Fast) => Color::Yellow,
+            Tile::Food(FoodKind::Slow) => Color::Blue,
+            Tile::Food(FoodKind::Shrink) => Color::Magenta,
+            Tile::Food(FoodKind::Double) => Color::Green,
+            Tile::Head(player) => PLAYER_COLORS[player % PLAYER_COLORS.len()].0,
+            Tile::Body(player) => PLAYER_COLORS[player % PLAYER_COLORS.len()].1,
+        }
+    }
+
+    // Two columns wide so cells come out about square. The food has roughly the shapes it has in the window
+    fn glyph(self) -> &'static str {
+        match self {
+            Tile::Empty => "  ",
+            Tile::Wall => "▒▒",
+            Tile::Food(FoodKind::Big) => "()",
+            Tile::Food(FoodKind::Fast) => "/\\",
+            Tile::Food(FoodKind::Slow) => "\\/",
+            Tile::Food(FoodKind::Shrink) => "[]",
+            Tile::Food(FoodKind::Double) => "<>",
+            Tile::Food(FoodKind::Normal) | Tile::Head(_) | Tile::Body(_) => "██",
+        }
+    }
+}
+
+// What's in every cell, row by row from the top
+fn tiles(game: &Game) -> Vec<Vec<Tile>> {
+    let grid = game.grid();
+    let mut tiles = vec![vec![Tile::Empty; grid.width as usize]; grid.height as usize];
+    let mut set = |x: i32, y: i32, tile: Tile| tiles[(grid.height - 1 - y) as usize][x as usize] = tile;
+    for wall in &game.level().walls {
+        set(wall.x, wall.y, Tile::Wall);
+    }
+    for food in game.food().iter().filter(|food| !food_blinked_out(food)) {
+        set(food.cell.x, food.cell.y, Tile::Food(food.kind));
+    }
+    for (player, snake) in game.snakes().iter().enumerate().filter(|(_, snake)| snake.is_alive()) {
+        for segment in snake.body() {
+            set(segment.x, segment.y, Tile::Body(player));
+        }
+        set(snake.head().x, snake.head().y, Tile::Head(player));
+    }
+    tiles
+}
+
+// Two cells on top of each other in one character, for boards too big to fit otherwise. Only colors, no shapes
+fn half_blocks(top: Tile, bottom: Tile) -> String {
+    match (top, bottom) {
+        (Tile::Empty, Tile::Empty) => " ".to_string(),
+        (top, Tile::Empty) => "▀".with(top.color()).to_string(),
+        (Tile::Empty, bottom) => "▄".with(bottom.color()).to_string(),
+        (top, bottom) => "▀".with(top.color()).on(bottom.color()).to_string(),
+    }
+}
+
+// A line of the screen with the color codes already in it, and how many columns it takes up
+struct Line {
+    text: String,
+    width: usize,
+}
+
+impl Line {
+    fn plain(text: &str) -> Line {
+        Line { text: text.to_string(), width: text.chars().count() }
+    }
+}
+
+// The board in a box. The box is dotted when the edges wrap around
+fn board(game: &Game, compact: bool) -> Vec<Line> {
+    let (horizontal, vertical) = match game.boundary() {
+        Boundary::Solid => ("─", "│"),
+        Boundary::Wrap => ("┄", "┆"),
+    };
+    let width = game.grid().width as usize * if compact { 1 } else { 2 };
+    let edge = |left: &str, right: &str| Line::plain(&format!("{}{}{}", left, horizontal.repeat(width), right));
+
+    let tiles = tiles(game);
+    let rows: Vec<String> = if compact {
+        tiles
+            .chunks(2)
+            .map(|pair| {
+                let bottom = |x: usize| pair.get(1).map_or(Tile::Empty, |row| row[x]);
+                pair[0].iter().enumerate().map(|(x, top)| half_blocks(*top, bottom(x))).collect()
+            })
+            .collect()
+    } else {
+        let glyph = |tile: &Tile| match tile {
+            Tile::Empty => tile.glyph().to_string(),
+            _ => tile.glyph().with(tile.color()).to_string(),
+        };
+        tiles.iter().map(|row| row.iter().map(glyph).collect()).collect()
+    };
+
+    let mut lines = vec![edge("┌", "┐")];
+    for row in rows {
+        lines.push(Line { text: format!("{}{}{}", vertical, row, vertical), width: width + 2 });
+    }
+    lines.push(edge("└", "┘"));
+    lines
+}
+
+// Arrow keys steer the first player and WASD the second one in two player games
+fn steering(code: KeyCode, two_players: bool) -> Option<(usize, Direction)> {
+    match code {
+        KeyCode::Up => Some((0, Direction::Up)),
+        KeyCode::Down => Some((0, Direction::Down)),
+        KeyCode::Left => Some((0, Direction::Left)),
+        KeyCode::Right => Some((0, Direction::Right)),
+        KeyCode::Char('w') if two_players => Some((1, Direction::Up)),
+        KeyCode::Char('s') if two_players => Some((1, Direction::Down)),
+        KeyCode::Char('a') if two_players => Some((1, Direction::Left)),
+        KeyCode::Char('d') if two_players => Some((1, Direction::Right)),
+        _ => None,
+    }
+}
+
+struct Tui {
+    driver: Driver,
+    quit: bool,
+}
+
+impl Tui {
+    fn new(options: &Options) -> Tui {
+        Tui { driver: Driver::new(load_levels(), options.seed), quit: false }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        // Raw mode gets Ctrl+C as a key instead of stopping the program
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            self.quit = true;
+            return;
+        }
+        let code = match key.code {
+            KeyCode::Char(c) => KeyCode::Char(c.to_ascii_lowercase()),
+            code => code,
+        };
+
+        // In two player games WASD belongs to the second player, so A doesn't toggle the autopilot mid game
+        let driver = &mut self.driver;
+        let two_players = driver.session().config().players > 1;
+        if driver.session().state() == State::Playing {
+            if let Some((player, direction)) = steering(code, two_players) {
+                driver.steer(player, direction);
+                return;
+            }
+        }
+
+        match (driver.session().state(), code) {
+            (_, KeyCode::Char('q')) => self.quit = true,
+            (State::Title | State::Playing, KeyCode::Char('a')) => driver.toggle_autopilot(),
+            (State::Title, KeyCode::Char('b')) => driver.next_strategy(),
+            (State::Title, KeyCode::Enter | KeyCode::Char(' ')) => driver.session_mut().start(),
+            (State::Title, KeyCode::Left) => driver.change_level(-1),
+            (State::Title, KeyCode::Right) => driver.change_level(1),
+            (State::Title, KeyCode::Char('1')) => driver.session_mut().set_players(1),
+            (State::Title, KeyCode::Char('2')) => driver.session_mut().set_players(2),
+            (State::Title, KeyCode::Char('d')) => driver.next_difficulty(),
+            (State::Title, KeyCode::Char('w')) => driver.toggle_boundary(),
+            (State::Playing | State::Paused, KeyCode::Char('p') | KeyCode::Esc) => driver.session_mut().toggle_pause(),
+            (State::Playing | State::Paused | State::GameOver, KeyCode::Char('r')) => driver.restart(),
+            (State::GameOver, KeyCode::Enter) => driver.restart(),
+            (State::GameOver | State::HighScores, KeyCode::Esc) => driver.back_to_title(),
+            _ => {}
+        }
+    }
+
+    fn title_lines(&self) -> Vec<Line> {
+        let driver = &self.driver;
+        let config = driver.session().config();
+        let edges = match config.boundary {
+            Boundary::Solid => "solid",
+            Boundary::Wrap => "wrap around",
+        };
+        let difficulty = Difficulty::of(driver.speed_curve()).map_or("Custom", Difficulty::name);
+        let players = match config.players {
+            1 => "1 player".to_string(),
+            players => format!("{} players, arrows against WASD", players),
+        };
+        let autopilot = if driver.autopilot() {
+            format!("Autopilot: {}", driver.strategy().name())
+        } else {
+            "Autopilot: off".to_string()
+        };
+        let lines = [
+            "S N A K E".to_string(),
+            String::new(),
+            "Enter to start, Q to quit".to_string(),
+            String::new(),
+            format!("< {} >", driver.level().name),
+            format!("Edges: {}   Difficulty: {}", edges, difficulty),
+            format!("{}   {}", players, autopilot),
+            String::new(),
+            "Left and right to pick a level, W for the edges, D for the difficulty".to_string(),
+            "1 and 2 for the players, A for the autopilot, B to pick the bot".to_string(),
+        ];
+        lines.iter().map(|line| Line::plain(line)).collect()
+    }
+
+    fn hud(&self, game: &Game) -> String {
+        let scores = if game.snakes().len() > 1 {
+            scores_line(game, &PLAYER_NAMES)
+        } else {
+            format!("Score: {}   Length: {}", game.score(), game.snake().length())
+        };
+        let mut hud = format!("{}   Speed level {}", scores, self.driver.speed_curve().level(game) + 1);
+        if self.driver.autopilot() {
+            hud += &format!("   Autopilot: {}", self.driver.strategy().name());
+        }
+        hud
+    }
+
+    // Everything on the screen for a terminal that's `columns` by `rows`, top to bottom
+    fn lines(&self, columns: u16, rows: u16) -> Vec<Line> {
+        let state = self.driver.session().state();
+        if state == State::Title || state == State::HighScores {
+            return self.title_lines();
+        }
+
+        // Two columns per cell if there's room, otherwise two rows per character
+        let game = self.driver.session().game();
+        let grid = game.grid();
+        let (width, height) = (grid.width as u16, grid.height as u16);
+        let fits = |needed_columns: u16, needed_rows: u16| needed_columns <= columns && needed_rows <= rows;
+        let compact = if fits(width * 2 + 2, height + 2 + EXTRA_LINES) {
+            false
+        } else if fits(width + 2, height.div_ceil(2) + 2 + EXTRA_LINES) {
+            true
+        } else {
+            let needed = format!("at least {}x{}", width + 2, height.div_ceil(2) + 2 + EXTRA_LINES);
+            return vec![Line::plain("The terminal is too small for this level,"), Line::plain(&needed)];
+        };
+
+        let mut lines = vec![Line::plain(&self.hud(game))];
+        lines.extend(board(game, compact));
+        let (status, help) = match state {
+            State::Paused => ("PAUSED".to_string(), "P to resume, R to restart, Q to quit"),
+            State::GameOver => (game_over_title(game), "R to play again, Escape for the title screen, Q to quit"),
+            _ => (effects_line(game, &PLAYER_NAMES), "Arrows to turn, P to pause, A for the autopilot, Q to quit"),
+        };
+        lines.push(Line::plain(&status));
+        if lines.len() < rows as usize {
+            lines.push(Line::plain(help));
+        }
+        lines
+    }
+}
+
+// Puts the terminal back the way it was when dropped, also when something panics
+struct RawTerminal;
+
+impl RawTerminal {
+    fn enter() -> io::Result<RawTerminal> {
+        terminal::enable_raw_mode()?;
+        let raw_terminal = RawTerminal;
+        execute!(io::stdout(), EnterAlternateScreen, Hide)?;
+        Ok(raw_terminal)
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), ResetColor, Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+// What's on the terminal right now. Only lines that changed get sent again, a whole board is a lot of color codes
+#[derive(Default)]
+struct Screen {
+    shown: Vec<String>,
+    size: (u16, u16),
+}
+
+impl Screen {
+    fn show(&mut self, out: &mut impl Write, lines: &[Line], (columns, rows): (u16, u16)) -> io::Result<()> {
+        if (columns, rows) != self.size {
+            self.size = (columns, rows);
+            self.shown.clear();
+            queue!(out, Clear(ClearType::All))?;
+        }
+        // Centered both ways
+        let top = (rows as usize).saturating_sub(lines.len()) / 2;
+        let mut frame = vec![String::new(); rows as usize];
+        for (row, line) in frame.iter_mut().skip(top).zip(lines) {
+            *row = " ".repeat((columns as usize).saturating_sub(line.width) / 2) + &line.text;
+        }
+        for (y, row) in frame.iter().enumerate() {
+            if self.shown.get(y) != Some(row) {
+                queue!(out, MoveTo(0, y as u16), Clear(ClearType::CurrentLine), Print(row))?;
+            }
+        }
+        self.shown = frame;
+        out.flush()
+    }
+}
+
+// Runs until Q is pressed. Errors are from the terminal, the game itself can't fail
+pub fn run(options: &Options) -> io::Result<()> {
+    // Before raw mode, loading the levels can print about broken ones
+    let mut tui = Tui::new(options);
+    let _raw_terminal = RawTerminal::enter()?;
+    let mut out = io::stdout();
+    let mut screen = Screen::default();
+    let mut last_frame = Instant::now();
+    while !tui.quit {
+        // Keys as they come in until it's time for the next frame
+        let next_frame = last_frame + FRAME_LENGTH;
+        while let Some(timeout) = next_frame.checked_duration_since(Instant::now()) {
+            if !event::poll(timeout)? {
+                break;
+            }
+            // Some terminals also send key releases
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    tui.handle_key(key);
+                }
+            }
+        }
+
+        let now = Instant::now();
+        tui.driver.step(now.duration_since(last_frame).as_secs_f32());
+        last_frame = now;
+        let (columns, rows) = terminal::size()?;
+        screen.show(&mut out, &tui.lines(columns, rows), (columns, rows))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,307 @@
+// Reading and writing the pieces replay files and network messages are made of. All numbers are LEB128 varints,
+// strings are their length followed by the UTF-8 bytes, and a config is written as:
+//
+//   level: name width height spawn-x spawn-y direction wall-count (x y)...
+//   boundary food-count players weight-count food-weight...
+//
+// where the weights go in the order of `FoodKind::ALL`. Older layouts didn't have everything, they're numbered the
+// same as the replay versions they were used in
+
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::food::{self, FoodKind};
+use crate::game::{Config, MAX_PLAYERS};
+use crate::grid::{Boundary, Cell, Direction, Grid};
+use crate::level::{self, Level};
+
+// Newest config layout, the one that gets written
+pub const CONFIG_VERSION: u64 = 3;
+
+// Bigger ones could add up to more than fits in a u32
+const MAX_FOOD_WEIGHT: u32 = u32::MAX / FoodKind::ALL.len() as u32;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    Truncated,
+    Invalid(&'static str),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Truncated => write!(f, "it ends in the middle"),
+            Error::Invalid(what) => write!(f, "bad {}", what),
+        }
+    }
+}
+
+pub fn direction_code(direction: Direction) -> u64 {
+    match direction {
+        Direction::Up => 0,
+        Direction::Down => 1,
+        Direction::Left => 2,
+        Direction::Right => 3,
+    }
+}
+
+pub fn direction_from_code(code: u64) -> Option<Direction> {
+    match code {
+        0 => Some(Direction::Up),
+        1 => Some(Direction::Down),
+        2 => Some(Direction::Left),
+        3 => Some(Direction::Right),
+        _ => None,
+    }
+}
+
+pub fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+pub fn write_string(bytes: &mut Vec<u8>, string: &str) {
+    write_varint(bytes, string.len() as u64);
+    bytes.extend(string.as_bytes());
+}
+
+pub fn write_config(bytes: &mut Vec<u8>, config: &Config) {
+    let level = &config.level;
+    write_string(bytes, &level.name);
+    write_varint(bytes, level.grid.width as u64);
+    write_varint(bytes, level.grid.height as u64);
+    write_varint(bytes, level.spawn.x as u64);
+    write_varint(bytes, level.spawn.y as u64);
+    write_varint(bytes, direction_code(level.direction));
+    // Sorted so the same level always gives the same bytes
+    let mut walls: Vec<Cell> = level.walls.iter().copied().collect();
+    walls.sort_by_key(|wall| (wall.y, wall.x));
+    write_varint(bytes, walls.len() as u64);
+    for wall in walls {
+        write_varint(bytes, wall.x as u64);
+        write_varint(bytes, wall.y as u64);
+    }
+
+    let boundary = match config.boundary {
+        Boundary::Solid => 0,
+        Boundary::Wrap => 1,
+    };
+    write_varint(bytes, boundary);
+    // Same as `Game::new`, configs that ask for more can't be read back
+    write_varint(bytes, config.food_count.min(level.max_food()) as u64);
+    write_varint(bytes, config.players as u64);
+    write_varint(bytes, config.food_weights.len() as u64);
+    for weight in config.food_weights {
+        write_varint(bytes, weight as u64);
+    }
+}
+
+// Reads from the front of a byte slice
+pub struct Reader<'a> {
+    pub bytes: &'a [u8],
+}
+
+impl Reader<'_> {
+    pub fn varint(&mut self) -> Result<u64, Error> {
+        let mut value = 0;
+        for shift in (0..64).step_by(7) {
+            let (byte, rest) = self.bytes.split_first().ok_or(Error::Truncated)?;
+            self.bytes = rest;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(Error::Invalid("number too long"))
+    }
+
+    // Varint that has to fit in an i32 (and be positive), like coordinates
+    pub fn small(&mut self, what: &'static str) -> Result<i32, Error> {
+        i32::try_from(self.varint()?).map_err(|_| Error::Invalid(what))
+    }
+
+    pub fn direction(&mut self) -> Result<Direction, Error> {
+        direction_from_code(self.varint()?).ok_or(Error::Invalid("direction"))
+    }
+
+    pub fn bytes(&mut self, length: usize) -> Result<&[u8], Error> {
+        if self.bytes.len() < length {
+            return Err(Error::Truncated);
+        }
+        let (bytes, rest) = self.bytes.split_at(length);
+        self.bytes = rest;
+        Ok(bytes)
+    }
+
+    pub fn string(&mut self, what: &'static str) -> Result<String, Error> {
+        let length = self.varint()? as usize;
+        String::from_utf8(self.bytes(length)?.to_vec()).map_err(|_| Error::Invalid(what))
+    }
+
+    // Version 1 didn't have the player count (they were all single player) and before version 3 there was only
+    // normal food. Configs are checked the same way level files are, so a broken file or a server sending garbage
+    // can't make a game that doesn't fit in memory or can't be played
+    pub fn config(&mut self, version: u64) -> Result<Config, Error> {
+        let name = self.string("level name")?;
+        let grid = Grid::new(self.small("width")?, self.small("height")?);
+        let spawn = Cell::new(self.small("spawn")?, self.small("spawn")?);
+        let direction = self.direction()?;
+        let sizes = 1..=level::MAX_SIZE;
+        if !sizes.contains(&grid.width) || !sizes.contains(&grid.height) {
+            return Err(Error::Invalid("level size"));
+        }
+        if !grid.contains(spawn) {
+            return Err(Error::Invalid("spawn"));
+        }
+        let wall_count = self.varint()?;
+        let mut walls = HashSet::new();
+        for _ in 0..wall_count {
+            let wall = Cell::new(self.small("wall")?, self.small("wall")?);
+            if !grid.contains(wall) {
+                return Err(Error::Invalid("wall outside the level"));
+            }
+            walls.insert(wall);
+        }
+        // Like in level files the snake can't run into anything on the first tick
+        let first_step = spawn.step(direction);
+        if walls.contains(&spawn) || !grid.contains(first_step) || walls.contains(&first_step) {
+            return Err(Error::Invalid("spawn"));
+        }
+        let level = Level { name, grid, walls, spawn, direction };
+
+        let boundary = match self.varint()? {
+            0 => Boundary::Solid,
+            1 => Boundary::Wrap,
+            _ => return Err(Error::Invalid("boundary")),
+        };
+        let food_count = self.varint()?;
+        if food_count > level.max_food() as u64 {
+            return Err(Error::Invalid("food count"));
+        }
+        let players = if version >= 2 { self.varint()? } else { 1 };
+        if players == 0 || players > MAX_PLAYERS as u64 {
+            return Err(Error::Invalid("player count"));
+        }
+        let mut food_weights = food::CLASSIC_WEIGHTS;
+        if version >= 3 {
+            let count = self.varint()? as usize;
+            if count != FoodKind::ALL.len() {
+                return Err(Error::Invalid("food weights"));
+            }
+            for weight in &mut food_weights {
+                let value = u32::try_from(self.varint()?).ok().filter(|value| *value <= MAX_FOOD_WEIGHT);
+                *weight = value.ok_or(Error::Invalid("food weights"))?;
+            }
+        }
+        Ok(Config { level, boundary, food_count: food_count as usize, players: players as usize, food_weights })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A config the way it's written, without any of the checks `write_config` has
+    fn raw_config(grid: Grid, spawn: Cell, walls: &[Cell], food_count: u64, players: u64) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        write_string(&mut bytes, "Test");
+        for value in [grid.width, grid.height, spawn.x, spawn.y] {
+            write_varint(&mut bytes, value as u64);
+        }
+        write_varint(&mut bytes, direction_code(Direction::Right));
+        write_varint(&mut bytes, walls.len() as u64);
+        for wall in walls {
+            write_varint(&mut bytes, wall.x as u64);
+            write_varint(&mut bytes, wall.y as u64);
+        }
+        write_varint(&mut bytes, 0);
+        write_varint(&mut bytes, food_count);
+        write_varint(&mut bytes, players);
+        write_varint(&mut bytes, FoodKind::ALL.len() as u64);
+        for weight in food::DEFAULT_WEIGHTS {
+            write_varint(&mut bytes, weight as u64);
+        }
+        bytes
+    }
+
+    fn read_config(bytes: &[u8]) -> Result<Config, Error> {
+        Reader { bytes }.config(CONFIG_VERSION)
+    }
+
+    #[test]
+    fn varints() {
+        for value in [0, 1, 127, 128, 300, u32::MAX as u64, u64::MAX] {
+            let mut bytes = Vec::new();
+            write_varint(&mut bytes, value);
+            let mut reader = Reader { bytes: &bytes };
+            assert_eq!(reader.varint(), Ok(value));
+            assert!(reader.bytes.is_empty());
+        }
+        assert_eq!(Reader { bytes: &[0x80, 0x80] }.varint(), Err(Error::Truncated));
+        assert_eq!(Reader { bytes: &[0xff; 11] }.varint(), Err(Error::Invalid("number too long")));
+        assert_eq!(Reader { bytes: &[0x80, 0x80, 0x80, 0x80, 0x10] }.small("x"), Err(Error::Invalid("x")));
+    }
+
+    #[test]
+    fn strings() {
+        let mut bytes = Vec::new();
+        write_string(&mut bytes, "Pillars ünd more");
+        assert_eq!(Reader { bytes: &bytes }.string("name"), Ok("Pillars ünd more".to_string()));
+        assert_eq!(Reader { bytes: &bytes[..5] }.string("name"), Err(Error::Truncated));
+        assert_eq!(Reader { bytes: &[2, 0xc3, 0x28] }.string("name"), Err(Error::Invalid("name")));
+    }
+
+    #[test]
+    fn config_round_trip() {
+        let mut level = Level::open(Grid::new(20, 10));
+        level.walls.extend([Cell::new(0, 0), Cell::new(19, 9)]);
+        let food_weights = [1, 2, 3, 4, 5, 6];
+        let config = Config { level, boundary: Boundary::Wrap, food_count: 3, players: 4, food_weights };
+        let mut bytes = Vec::new();
+        write_config(&mut bytes, &config);
+        let mut reader = Reader { bytes: &bytes };
+        let read = reader.config(CONFIG_VERSION).unwrap();
+        assert!(reader.bytes.is_empty());
+        assert_eq!(read.level.name, "Open");
+        assert_eq!(read.level.grid, config.level.grid);
+        assert_eq!(read.level.walls, config.level.walls);
+        assert_eq!((read.level.spawn, read.level.direction), (config.level.spawn, config.level.direction));
+        assert_eq!((read.boundary, read.food_count, read.players), (Boundary::Wrap, 3, 4));
+        assert_eq!(read.food_weights, config.food_weights);
+
+        // More food than fits gets written as the most that does
+        let config = Config { food_count: 5000, ..Config::default() };
+        let mut bytes = Vec::new();
+        write_config(&mut bytes, &config);
+        assert_eq!(read_config(&bytes).unwrap().food_count, config.level.max_food());
+    }
+
+    #[test]
+    fn configs_are_checked() {
+        // Only the first error matters, the rest of the config is fine
+        let check = |grid: Grid, spawn: Cell, walls: &[Cell], food_count: u64, players: u64| {
+            read_config(&raw_config(grid, spawn, walls, food_count, players)).map(|_| ())
+        };
+        let (grid, spawn) = (Grid::new(10, 10), Cell::new(5, 5));
+        assert_eq!(check(grid, spawn, &[], 1, 2), Ok(()));
+
+        assert_eq!(check(Grid::new(level::MAX_SIZE + 1, 10), spawn, &[], 1, 1), Err(Error::Invalid("level size")));
+        assert_eq!(check(Grid::new(0, 10), spawn, &[], 1, 1), Err(Error::Invalid("level size")));
+
+        let outside = Cell::new(10, 5);
+        assert_eq!(check(grid, spawn, &[outside], 1, 1), Err(Error::Invalid("wall outside the level")));
+        assert_eq!(check(grid, outside, &[], 1, 1), Err(Error::Invalid("spawn")));
+        assert_eq!(check(grid, spawn, &[spawn], 1, 1), Err(Error::Invalid("spawn")));
+        // Heading right into a wall, or out of the board
+        assert_eq!(check(grid, spawn, &[Cell::new(6, 5)], 1, 1), Err(Error::Invalid("spawn")));
+        assert_eq!(check(grid, Cell::new(9, 5), &[], 1, 1), Err(Error::Invalid("spawn")));
+
+        assert_eq!(check(grid, spawn, &[], 99, 1), Ok(()));
+        assert_eq!(check(grid, spawn, &[], 100, 1), Err(Error::Invalid("food count")));
+        assert_eq!(check(grid, spawn, &[], 1, 0), Err(Error::Invalid("player count")));
+        assert_eq!(check(grid, spawn, &[], 1, MAX_PLAYERS as u64 + 1), Err(Error::Invalid("player count")));
+    }
+}
@@ -0,0 +1,138 @@
+// Runs a server and a few bot clients on localhost and checks that every client played exactly the game the server did
+
+use std::net::TcpStream;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use snake::ai::Strategy;
+use snake::game::Config;
+use snake::level::Level;
+use snake::net::{Client, Error, Server, ServerConfig, ServerEvent};
+use snake::replay::Player;
+
+const PLAYERS: usize = 3;
+const ROUNDS: usize = 2;
+
+// Ticks and scores of every round a client saw end. `lag` is how long it sleeps between looking for new frames
+fn play_as_bot(mut client: Client, strategy: Strategy, lag: Duration) -> Vec<(u32, Vec<u32>)> {
+    let mut controller = strategy.controller();
+    let mut rounds = Vec::new();
+    loop {
+        match client.receive() {
+            Ok(()) => {}
+            Err(Error::Closed) => return rounds,
+            Err(err) => panic!("client {} failed: {}", client.player(), err),
+        }
+        while client.tick() {
+            let game = client.game().expect("ticks only happen during a round");
+            if game.is_over() {
+                rounds.push((game.ticks(), game.snakes().iter().map(|snake| snake.score()).collect()));
+            } else if game.snakes()[client.player()].is_alive() {
+                let direction = controller.decide(game, client.player());
+                // Can fail once the server is done, that shows up in `receive`
+                let _ = client.turn(direction);
+            }
+        }
+        thread::sleep(lag);
+    }
+}
+
+#[test]
+fn bots_play_the_same_game_as_the_server() {
+    let level = Level::parse("map:\n............\n............\n.....S......\n............\n............")
+        .expect("the test level is valid");
+    let config = ServerConfig {
+        game: Config { level, food_count: 2, players: PLAYERS, ..Config::default() },
+        ticks_per_second: 200.,
+        input_delay: 2,
+        // No break, so the next round starts while the slower clients are still playing the last one
+        round_break: Duration::ZERO,
+        seed: 1,
+    };
+    let mut server = Server::bind("127.0.0.1:0", config).expect("can listen on localhost");
+    let address = server.local_addr().expect("the server has an address");
+    let (sender, events) = mpsc::channel();
+    server.on_event(move |event| {
+        let _ = sender.send(event);
+    });
+    let server = thread::spawn(move || {
+        let recordings: Vec<_> = (0..ROUNDS).map(|_| server.run_round()).collect();
+        // Dropping the server closes every connection, that's how the clients know it's over
+        drop(server);
+        recordings
+    });
+
+    let mut clients = Vec::new();
+    for i in 0..PLAYERS {
+        let client = Client::connect(address).expect("can join the server");
+        assert_eq!(client.player(), i);
+        let strategy = Strategy::ALL[i % Strategy::ALL.len()];
+        // A few ticks behind, so the end of a round and the next one's start come in together
+        let lag = Duration::from_millis(1 + 12 * i as u64);
+        clients.push(thread::spawn(move || play_as_bot(client, strategy, lag)));
+    }
+    // Everyone's there, so the next one has to wait outside
+    match Client::connect(address) {
+        Err(Error::Rejected(_)) => {}
+        Err(err) => panic!("expected the server to be full, got {}", err),
+        Ok(_) => panic!("expected the server to be full, but got in"),
+    }
+
+    let recordings = server.join().expect("the server doesn't panic");
+    let expected: Vec<(u32, Vec<u32>)> =
+        recordings.iter().map(|recording| (recording.ticks, recording.scores.clone())).collect();
+    for client in clients {
+        assert_eq!(client.join().expect("the clients don't panic"), expected);
+    }
+    let events: Vec<ServerEvent> = events.try_iter().collect();
+    let joined: Vec<usize> = events
+        .iter()
+        .filter_map(|event| match event {
+            ServerEvent::Joined { player, .. } => Some(*player),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(joined, (0..PLAYERS).collect::<Vec<_>>());
+    assert!(events.iter().any(|event| matches!(event, ServerEvent::Refused { error: Error::Rejected(_), .. })));
+
+    // The rounds are also proper recordings that play back the same
+    for recording in recordings {
+        let mut player = Player::new(recording.clone());
+        player.finish();
+        let scores: Vec<u32> = player.game().snakes().iter().map(|snake| snake.score()).collect();
+        assert_eq!(scores, recording.scores);
+    }
+}
+
+#[test]
+fn levels_without_room_for_everyone_are_refused() {
+    // The second snake would have to start right in front of the first one
+    let level = Level::parse("map:\n.S.").expect("the test level is valid");
+    let game = Config { level, players: 2, ..Config::default() };
+    assert!(Server::bind("127.0.0.1:0", ServerConfig { game, ..ServerConfig::default() }).is_err());
+}
+
+#[test]
+fn silent_connections_dont_hold_up_the_game() {
+    let level = Level::parse("map:\n......\n..S...\n......").expect("the test level is valid");
+    let config = ServerConfig {
+        game: Config { level, players: 1, ..Config::default() },
+        ticks_per_second: 200.,
+        round_break: Duration::ZERO,
+        ..ServerConfig::default()
+    };
+    let mut server = Server::bind("127.0.0.1:0", config).expect("can listen on localhost");
+    let address = server.local_addr().expect("the server has an address");
+    // Connects first but never says hello, the server mustn't wait for it before letting anyone else in
+    let _silent = TcpStream::connect(address).expect("can connect to the server");
+    let start = Instant::now();
+    let server = thread::spawn(move || server.run_round());
+
+    let client = Client::connect(address).expect("can join the server");
+    let played = thread::spawn(move || play_as_bot(client, Strategy::ALL[0], Duration::from_millis(1)));
+    let recording = server.join().expect("the server doesn't panic");
+    // Well under the handshake timeout the silent one would have held everything up for
+    assert!(start.elapsed() < Duration::from_secs(3));
+    assert_eq!(played.join().expect("the client doesn't panic"), vec![(recording.ticks, recording.scores)]);
+}
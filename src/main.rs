@@ -0,0 +1,217 @@
+use std::process;
+use std::sync::OnceLock;
+use std::time::Instant;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use nannou::prelude::*;
+use nannou_egui::{egui, Egui};
+use support::{Bounds, HasEgui, Input, Options, Sketch};
+
+use crate::sketch::SketchKind;
+
+mod sketch;
+
+#[derive(Parser)]
+#[command(name = "nannou-projects", version, about = "Runs any of the nannou sketches from this repo")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Open a sketch, you can switch to the others from the settings window
+    Run(RunArgs),
+    /// Step a sketch without opening a window, works on machines without a display or GPU
+    Simulate(SimulateArgs),
+    /// List the sketches that can be run
+    List,
+}
+
+#[derive(Args)]
+struct SketchArgs {
+    /// Which sketch to use
+    sketch: SketchKind,
+    /// Amount of stars, drops or cells the sketch starts with
+    #[arg(long)]
+    amount: Option<usize>,
+    /// Seed for the sketch's RNG, runs with the same seed are the same. Random if not set
+    #[arg(long)]
+    seed: Option<u64>,
+}
+
+impl SketchArgs {
+    fn options(&self) -> Options {
+        Options { amount: self.amount, seed: self.seed.unwrap_or_else(support::random_seed) }
+    }
+}
+
+#[derive(Args)]
+struct RunArgs {
+    #[command(flatten)]
+    sketch: SketchArgs,
+    /// Play in the terminal instead of a window, works over SSH. Only snake has this
+    #[arg(long)]
+    tui: bool,
+}
+
+#[derive(Args)]
+struct SimulateArgs {
+    #[command(flatten)]
+    sketch: SketchArgs,
+    /// How many steps to run, each one is 1/60th of a second
+    #[arg(long, default_value_t = 600)]
+    steps: u32,
+    /// Width of the simulated area, defaults to the sketch's window width
+    #[arg(long)]
+    width: Option<u32>,
+    /// Height of the simulated area, defaults to the sketch's window height
+    #[arg(long)]
+    height: Option<u32>,
+}
+
+// nannou wants a plain fn for the model, so the arguments get passed to it through here
+static RUN_ARGS: OnceLock<SketchArgs> = OnceLock::new();
+
+struct Model {
+    kind: SketchKind,
+    options: Options, // What the current sketch was created with
+    sketch: Box<dyn Sketch>,
+    egui: Egui,
+}
+
+impl HasEgui for Model {
+    fn egui(&mut self) -> &mut Egui {
+        &mut self.egui
+    }
+}
+
+fn main() {
+    match Cli::parse().command {
+        Command::List => {
+            for kind in SketchKind::value_variants() {
+                println!("{}", kind.name());
+            }
+        }
+        Command::Run(args) if args.tui => run_in_terminal(&args.sketch),
+        Command::Run(args) => {
+            let _ = RUN_ARGS.set(args.sketch);
+            nannou::app(model).update(update).run();
+        }
+        Command::Simulate(args) => simulate(&args),
+    }
+}
+
+// Snake can also be played with text in a terminal, the other sketches need a window
+fn run_in_terminal(args: &SketchArgs) {
+    if args.sketch != SketchKind::Snake {
+        eprintln!("{} can't run in a terminal, --tui only works for snake", args.sketch.name());
+        process::exit(1);
+    }
+    if let Err(err) = snake::tui::run(&args.options()) {
+        eprintln!("Terminal error: {}", err);
+        process::exit(1);
+    }
+}
+
+// Runs the sketch with a fixed time step and no nannou app at all
+fn simulate(args: &SimulateArgs) {
+    let kind = args.sketch.sketch;
+    let (width, height) = kind.size();
+    let bounds = Bounds::new(args.width.unwrap_or(width), args.height.unwrap_or(height));
+
+    let options = args.sketch.options();
+    let start = Instant::now();
+    let mut sketch = kind.create(bounds, &options);
+    support::step_headless(sketch.as_mut(), bounds, args.steps);
+
+    println!("{}: {} steps at {}x{} with seed {} in {:?}", kind.name(), args.steps, bounds.width, bounds.height, options.seed, start.elapsed());
+}
+
+fn model(app: &App) -> Model {
+    let args = RUN_ARGS.get().expect("run arguments are set before the app starts");
+    let (width, height) = args.sketch.size();
+
+    let window = app
+        .new_window()
+        .title("nannou-projects")
+        .size(width, height)
+        .view(view)
+        .key_pressed(key_pressed)
+        .received_character(received_character)
+        .mouse_pressed(mouse_pressed);
+    let egui = support::egui_window::<Model>(app, window);
+
+    let bounds = Bounds::from(app.main_window().inner_size_points());
+    let options = args.options();
+    let sketch = args.sketch.create(bounds, &options);
+    Model { kind: args.sketch, options, sketch, egui }
+}
+
+fn key_pressed(_app: &App, model: &mut Model, key: Key) {
+    model.sketch.handle_input(&Input::KeyPressed(key));
+}
+
+fn received_character(_app: &App, model: &mut Model, c: char) {
+    model.sketch.handle_input(&Input::Char(c));
+}
+
+fn mouse_pressed(app: &App, model: &mut Model, button: MouseButton) {
+    model.sketch.handle_input(&Input::MousePressed(button, app.mouse.position()));
+}
+
+fn update(app: &App, model: &mut Model, update: Update) {
+    let egui = &mut model.egui;
+    egui.set_elapsed_time(update.since_start);
+    let ctx = egui.begin_frame();
+
+    let mut kind = model.kind;
+    let mut restart = false;
+    let mut new_seed = false;
+    egui::Window::new("Settings").show(&ctx, |ui| {
+        egui::ComboBox::from_label("Sketch")
+            .selected_text(kind.name())
+            .show_ui(ui, |ui| {
+                for variant in SketchKind::value_variants() {
+                    ui.selectable_value(&mut kind, *variant, variant.name());
+                }
+            });
+        // Shown so interesting runs can be shared with --seed
+        ui.label(format!("Seed: {}", model.options.seed));
+        ui.horizontal(|ui| {
+            restart = ui.button("Restart").clicked();
+            new_seed = ui.button("New seed").clicked();
+        });
+        ui.separator();
+        model.sketch.settings_ui(ui);
+    });
+
+    let bounds = Bounds::from(app.main_window().inner_size_points());
+
+    // Switching throws the old sketch away, the --amount from the command line only applies to the first one
+    if kind != model.kind {
+        model.kind = kind;
+        model.options = Options { amount: None, seed: support::random_seed() };
+        restart = true;
+    } else if new_seed {
+        model.options.seed = support::random_seed();
+        restart = true;
+    }
+    if restart {
+        model.sketch = kind.create(bounds, &model.options);
+    }
+
+    model.sketch.step(bounds, update.since_last.as_secs_f32());
+}
+
+fn view(app: &App, model: &Model, frame: Frame) {
+    let draw = app.draw();
+    draw.background().color(BLACK);
+
+    model.sketch.draw(&draw);
+
+    // Draw everything to the frame
+    draw.to_frame(app, &frame).unwrap();
+    // Overlay the gui
+    model.egui.draw_to_frame(&frame).unwrap();
+}
@@ -0,0 +1,45 @@
+use clap::ValueEnum;
+use support::{Bounds, Options, Sketch};
+
+// Every sketch that can be launched, the names on the command line are the kebab-case versions
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum SketchKind {
+    Snake,
+    Mitosis,
+    PurpleRain,
+    Stars,
+    Starfield,
+}
+
+impl SketchKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            SketchKind::Snake => "snake",
+            SketchKind::Mitosis => "mitosis",
+            SketchKind::PurpleRain => "purple-rain",
+            SketchKind::Stars => "stars",
+            SketchKind::Starfield => "starfield",
+        }
+    }
+
+    // Window size the sketch was made for
+    pub fn size(self) -> (u32, u32) {
+        match self {
+            SketchKind::Snake => snake::SIZE,
+            SketchKind::Mitosis => mitosis::SIZE,
+            SketchKind::PurpleRain => purple_rain::SIZE,
+            SketchKind::Stars => stars::SIZE,
+            SketchKind::Starfield => starfield::SIZE,
+        }
+    }
+
+    pub fn create(self, bounds: Bounds, options: &Options) -> Box<dyn Sketch> {
+        match self {
+            SketchKind::Snake => Box::new(snake::Model::init(bounds, options)),
+            SketchKind::Mitosis => Box::new(mitosis::Model::init(bounds, options)),
+            SketchKind::PurpleRain => Box::new(purple_rain::Model::init(bounds, options)),
+            SketchKind::Stars => Box::new(stars::Model::init(bounds, options)),
+            SketchKind::Starfield => Box::new(starfield::Model::init(bounds, options)),
+        }
+    }
+}
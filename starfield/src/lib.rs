@@ -0,0 +1,117 @@
+use nannou::prelude::*;
+use nannou::rand::Rng;
+use nannou_egui::egui;
+use support::{Bounds, Options, Sketch, SimRng};
+
+struct Star {
+    coords: Point2,
+    prev_coords: Point2,
+}
+
+impl Star {
+    fn new(rng: &mut impl Rng, bounds: Bounds) -> Star {
+        let coords = support::random_point(rng, bounds);
+        Star {coords, prev_coords: coords }
+    }
+
+    fn update(&mut self, rng: &mut impl Rng, bounds: Bounds) {
+
+        if !(self.coords.x < (bounds.width/2) as f32 && self.coords.x > -((bounds.width / 2) as f32)) &&
+            !(self.coords.y < (bounds.height/2) as f32 && self.coords.y > -((bounds.width / 2) as f32)) {
+            self.coords = support::random_point(rng, bounds);
+            self.prev_coords = self.coords;
+            return;
+            }
+
+        self.prev_coords = self.coords;
+        let offset = rng.gen_range(1. .. 15.);
+        if self.coords.x > 0. {
+            // 1st quadrant
+            if self.coords.y > 0. {
+                self.coords.x += offset;
+                self.coords.y += offset+1.; // TODO: make a slider for this
+            } else { // 4th quadrant
+                self.coords.x += offset;
+                self.coords.y -= offset+1.;
+            }
+        } else {
+            if self.coords.y > 0. { // 2nd quadrant
+                self.coords.x -= offset;
+                self.coords.y += offset+1.;
+            } else { // 3rd quadrant
+                self.coords.x -= offset;
+                self.coords.y -= offset+1.;
+            }
+        }
+    }
+}
+
+// Size of the window the sketch was made for
+pub const SIZE: (u32, u32) = (1920, 1080);
+
+pub struct Model {
+    settings: Settings,
+    stars: Vec<Star>,
+    rng: SimRng,
+}
+
+struct Settings {
+    amount: usize,
+}
+
+impl Sketch for Model {
+    // `amount` overrides the default amount of stars
+    fn init(_bounds: Bounds, options: &Options) -> Model {
+        let stars = Vec::new();
+        let rng = support::seeded_rng(options.seed);
+        Model { settings: Settings { amount: options.amount.unwrap_or(150) }, stars, rng }
+    }
+
+    fn step(&mut self, bounds: Bounds, _dt: f32) {
+        let settings = &self.settings;
+
+        if settings.amount > self.stars.len() {
+            for _ in self.stars.len()..settings.amount {
+                self.stars.push(Star::new(&mut self.rng, bounds))
+            }
+        } else if settings.amount < self.stars.len() { // If there are more stars than needed
+            self.stars.drain(settings.amount..self.stars.len());
+        }
+
+        for star in &mut self.stars {
+            star.update(&mut self.rng, bounds);
+        }
+    }
+
+    fn draw(&self, draw: &Draw) {
+        for star in self.stars.iter() {
+            draw.line().start(star.prev_coords).end(star.coords).color(WHITE).stroke_weight(1.);
+        }
+    }
+
+    fn settings_ui(&mut self, ui: &mut egui::Ui) {
+        ui.label("Amount:");
+        ui.add(egui::Slider::new(&mut self.settings.amount, 1..=10000)); // Value, Limit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Same as `nannou-projects simulate`, no window needed
+    #[test]
+    fn runs_headless() {
+        let bounds = Bounds::new(SIZE.0, SIZE.1);
+        let model: Model = support::run_headless(bounds, &Options { amount: Some(50), seed: 1 }, &[], 600);
+        assert_eq!(model.stars.len(), 50);
+    }
+
+    #[test]
+    fn same_seed_same_run() {
+        let options = Options { amount: Some(50), seed: 7 };
+        support::assert_same_seed_same_run(Bounds::new(SIZE.0, SIZE.1), &options, &[], |model: &Model| {
+            model.stars.iter().map(|star| star.coords).collect::<Vec<_>>()
+        });
+    }
+}
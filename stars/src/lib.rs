@@ -0,0 +1,137 @@
+use std::fmt::{Display, Formatter};
+use nannou::prelude::*;
+use nannou_egui::egui;
+use nannou::rand::Rng;
+use support::{Bounds, Options, Sketch, SimRng};
+
+// Stores settings for egui
+struct Settings {
+    amount: usize,
+    color: Srgba<f32>,
+    min_decrease: f32,
+    max_decrease: f32,
+    radius: f32,
+}
+
+// Stores the coordinates and the alpha value, we could've used a Point2D too
+struct Star {
+    x: f32,
+    y: f32,
+    a: f32,
+}
+
+impl Star {
+    // Create a new star
+    fn new(rng: &mut impl Rng, bounds: Bounds) -> Star {
+        let pos = support::random_point(rng, bounds);
+        Star { x: pos.x, y: pos.y, a: 1. }
+    }
+
+    // Updates the "star" alpha, if the star doesn't exist anymore teleport it in another position, completely opaque
+    fn update(&mut self, rng: &mut impl Rng, bounds: Bounds, min_decrease: f32, max_decrease: f32) {
+        self.a = self.a - rng.gen_range(min_decrease..max_decrease);
+        if self.a < 0. {
+            self.a = 1.;
+            self.x = support::random_coord(rng, bounds.width * 2);
+            self.y = support::random_coord(rng, bounds.height * 2);
+        }
+    }
+}
+
+impl Display for Star {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f,"Star x: {} y: {} alpha: {}", self.x, self.y, self.a)
+    }
+}
+
+// Size of the window the sketch was made for
+pub const SIZE: (u32, u32) = (1920, 1080);
+
+// We need to store the stars, settings and the rng everything random comes from
+pub struct Model {
+    stars: Vec<Star>,
+    settings: Settings,
+    rng: SimRng,
+}
+
+impl Sketch for Model {
+    // `amount` overrides the default amount of stars
+    fn init(_bounds: Bounds, options: &Options) -> Model {
+        let stars = Vec::new();
+        let rng = support::seeded_rng(options.seed);
+
+        // Default values
+        Model {settings: Settings{amount: options.amount.unwrap_or(100), color: srgba(1.,1.,1.,1.), radius: 8., min_decrease: 0.001, max_decrease: 0.1 }, stars, rng}
+    }
+
+    fn step(&mut self, bounds: Bounds, _dt: f32) {
+        let settings = &self.settings;
+
+        // This is before the star amount check because we don't need to update the stars twice on the same frame
+        // I.E. if we increase the star amount, we do not need to go over this part of the vector again
+        for star in &mut self.stars {
+            star.update(&mut self.rng, bounds, settings.min_decrease, settings.max_decrease);
+        }
+
+        // If there are less stars than needed
+        if settings.amount > self.stars.len() {
+            for _ in self.stars.len()..settings.amount {
+                self.stars.push(Star::new(&mut self.rng, bounds))
+            }
+        } else if settings.amount < self.stars.len() { // If there are more stars than needed
+            self.stars.drain(settings.amount..self.stars.len());
+        }
+    }
+
+    fn draw(&self, draw: &Draw) {
+        // Draw each star
+        for star in &self.stars {
+            let r = self.settings.radius;
+            let mut color = self.settings.color;
+            color.alpha = star.a; // Change alpha to create fade effect
+            draw.ellipse().x(star.x).y(star.y).color(color).radius(r);
+        }
+    }
+
+    // EGUI Menu
+    fn settings_ui(&mut self, ui: &mut egui::Ui) {
+        let settings = &mut self.settings;
+        ui.label("Amount:");
+        ui.add(egui::Slider::new(&mut settings.amount, 1..=10000)); // Value, Limit
+        ui.label("Min Decrease amount:");
+        ui.add(egui::Slider::new(&mut settings.min_decrease, 0. ..= settings.max_decrease-0.0000001));
+        ui.label("Max Decrease amount:");
+        ui.add(egui::Slider::new(&mut settings.max_decrease, settings.min_decrease+0.0000001 ..= 1.));
+        ui.label("Radius:");
+        ui.add(egui::Slider::new(&mut settings.radius, 0.5 ..= 64.));
+
+
+        let clicked = ui.button("Random color").clicked();
+
+        if clicked {
+            settings.color = srgba(self.rng.gen(),self.rng.gen(),self.rng.gen(), 1.);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Same as `nannou-projects simulate`, no window needed
+    #[test]
+    fn runs_headless() {
+        let bounds = Bounds::new(SIZE.0, SIZE.1);
+        let model: Model = support::run_headless(bounds, &Options { amount: Some(50), seed: 1 }, &[], 600);
+        assert_eq!(model.stars.len(), 50);
+        assert!(model.stars.iter().all(|star| (0. ..=1.).contains(&star.a)));
+    }
+
+    #[test]
+    fn same_seed_same_run() {
+        let options = Options { amount: Some(50), seed: 7 };
+        support::assert_same_seed_same_run(Bounds::new(SIZE.0, SIZE.1), &options, &[], |model: &Model| {
+            model.stars.iter().map(|star| (star.x, star.y, star.a)).collect::<Vec<_>>()
+        });
+    }
+}
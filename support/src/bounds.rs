@@ -0,0 +1,28 @@
+// Size of the area a simulation runs in, in points like everything nannou draws (on screens with scaling a point is
+// more than one pixel). Like in nannou the origin is in the middle, so coordinates go from -width/2 to width/2
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Bounds {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Bounds {
+    pub fn new(width: u32, height: u32) -> Bounds {
+        Bounds { width, height }
+    }
+
+    pub fn half_width(&self) -> f32 {
+        self.width as f32 / 2.
+    }
+
+    pub fn half_height(&self) -> f32 {
+        self.height as f32 / 2.
+    }
+}
+
+// So `inner_size_points()` can be passed straight in
+impl From<(f32, f32)> for Bounds {
+    fn from((width, height): (f32, f32)) -> Bounds {
+        Bounds { width: width.round() as u32, height: height.round() as u32 }
+    }
+}
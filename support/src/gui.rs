@@ -0,0 +1,26 @@
+use nannou::prelude::*;
+use nannou::window;
+use nannou::winit::event::WindowEvent;
+use nannou_egui::Egui;
+
+// Implemented by every model that owns an Egui instance, so the raw event forwarding can be shared
+pub trait HasEgui {
+    fn egui(&mut self) -> &mut Egui;
+}
+
+// Builds the window (with whatever view, size, etc. was set on it), makes it forward its raw events to egui
+// and returns the Egui attached to it
+pub fn egui_window<M: HasEgui + 'static>(app: &App, window: window::Builder) -> Egui {
+    let window_id = window
+        .raw_event(raw_window_event::<M>)
+        .build()
+        .unwrap();
+
+    let window = app.window(window_id).unwrap();
+    Egui::from_window(&window)
+}
+
+pub fn raw_window_event<M: HasEgui>(_app: &App, model: &mut M, event: &WindowEvent) {
+    // Let egui handle things like keyboard and mouse input.
+    model.egui().handle_raw_event(event);
+}
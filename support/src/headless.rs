@@ -0,0 +1,46 @@
+use std::fmt::Debug;
+
+use nannou::prelude::*;
+
+use crate::{Bounds, Input, Options, Sketch};
+
+// Steps are this long when there's no window to go by, same as a window at 60 fps
+const HEADLESS_DT: f32 = 1. / 60.;
+// How long the runs `assert_same_seed_same_run` compares are, five seconds
+const COMPARED_STEPS: u32 = 300;
+
+// Runs `steps` steps without a window, then draws the final state to make sure drawing works too (it doesn't need a
+// window either)
+pub fn step_headless(sketch: &mut dyn Sketch, bounds: Bounds, steps: u32) {
+    for _ in 0..steps {
+        sketch.step(bounds, HEADLESS_DT);
+    }
+    sketch.draw(&Draw::new());
+}
+
+// A new sketch that got `inputs` and then ran `steps` steps, for tests. Same as `nannou-projects simulate`
+pub fn run_headless<M: Sketch>(bounds: Bounds, options: &Options, inputs: &[Input], steps: u32) -> M {
+    let mut sketch = M::init(bounds, options);
+    for input in inputs {
+        sketch.handle_input(input);
+    }
+    step_headless(&mut sketch, bounds, steps);
+    sketch
+}
+
+// Checks that a sketch only depends on its seed: two runs with `options.seed` have to end the same, and a run with
+// another seed has to end differently. `state` is what gets compared, so every sketch picks what matters in it
+pub fn assert_same_seed_same_run<M: Sketch, T: PartialEq + Debug>(
+    bounds: Bounds,
+    options: &Options,
+    inputs: &[Input],
+    state: impl Fn(&M) -> T,
+) {
+    let run = |seed: u64| {
+        let options = Options { seed, ..options.clone() };
+        state(&run_headless::<M>(bounds, &options, inputs, COMPARED_STEPS))
+    };
+    let first = run(options.seed);
+    assert_eq!(first, run(options.seed), "the same seed gave a different run");
+    assert_ne!(first, run(options.seed.wrapping_add(1)), "another seed gave the same run");
+}
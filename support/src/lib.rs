@@ -0,0 +1,13 @@
+// Things that every sketch used to copy around, kept here so they only get written (and compiled) once
+
+mod bounds;
+mod gui;
+mod headless;
+mod random;
+mod sketch;
+
+pub use bounds::Bounds;
+pub use gui::{egui_window, raw_window_event, HasEgui};
+pub use headless::{assert_same_seed_same_run, run_headless, step_headless};
+pub use random::{random_coord, random_point, random_seed, seeded_rng, SimRng};
+pub use sketch::{Input, Options, Sketch};
@@ -0,0 +1,34 @@
+use nannou::prelude::*;
+use nannou::rand::rand::{self, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::Bounds;
+
+// The RNG every simulation owns. It's seeded so a run can be reproduced, and ChaCha8 is used instead of StdRng
+// because StdRng is allowed to change its algorithm between rand versions
+pub type SimRng = ChaCha8Rng;
+
+pub fn seeded_rng(seed: u64) -> SimRng {
+    SimRng::seed_from_u64(seed)
+}
+
+// Seed for when none was given, this is the only place that should use the thread rng
+pub fn random_seed() -> u64 {
+    rand::random()
+}
+
+// Random coordinate on an axis that is `size` points long. Since nannou handles coords from the center
+// this goes from -size/2 to size/2
+pub fn random_coord(rng: &mut impl Rng, size: u32) -> f32 {
+    let half = (size / 2) as i32;
+    if half == 0 {
+        // Minimised windows report a size of 0, and gen_range panics on empty ranges
+        return 0.;
+    }
+    rng.gen_range(-half..half) as f32
+}
+
+// Random point somewhere inside the bounds
+pub fn random_point(rng: &mut impl Rng, bounds: Bounds) -> Point2 {
+    Point2::new(random_coord(rng, bounds.width), random_coord(rng, bounds.height))
+}
@@ -0,0 +1,42 @@
+use nannou::prelude::*;
+use nannou_egui::egui;
+
+use crate::Bounds;
+
+// Options from the command line that every sketch gets, the ones that don't apply to a sketch are ignored
+#[derive(Clone)]
+pub struct Options {
+    // Amount of stars, drops, cells, etc. to start with
+    pub amount: Option<usize>,
+    // Seed for the sketch's RNG, the same seed (and input) gives exactly the same run
+    pub seed: u64,
+}
+
+// Input the host forwards from the window, so sketches don't need their own nannou callbacks
+pub enum Input {
+    KeyPressed(Key),
+    // Text typed with the keyboard layout applied, for typing in names and such
+    Char(char),
+    // The position is where the mouse was, in the same coordinates the sketch draws in
+    MousePressed(MouseButton, Point2),
+}
+
+// The nannou model/update/view/event shape every sketch used to implement by hand, so one host can drive any of them.
+// Nothing in here needs a window, the state only changes through `step` with the bounds the host passes in, so
+// sketches can also be stepped headless (in CI, tests, etc.)
+pub trait Sketch {
+    fn init(bounds: Bounds, options: &Options) -> Self
+    where
+        Self: Sized;
+
+    // Advance the simulation, `dt` is the time since the last step in seconds
+    fn step(&mut self, bounds: Bounds, dt: f32);
+
+    // Draw the current state, the host has already cleared the background
+    fn draw(&self, draw: &Draw);
+
+    fn handle_input(&mut self, _input: &Input) {}
+
+    // Contents of the settings window, the host owns the window itself
+    fn settings_ui(&mut self, _ui: &mut egui::Ui) {}
+}